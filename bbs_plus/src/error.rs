@@ -1,7 +1,8 @@
 // TODO: At some point this should be replaced with crates anyhow and thiserror but thiserror is no_std compatible at the moment.
 
+use crate::threshold::randomness_generation_phase::MIN_SALT_SIZE;
 use ark_serialize::SerializationError;
-use ark_std::fmt::Debug;
+use ark_std::fmt::{self, Debug, Display, Formatter};
 use dock_crypto_utils::{
     serde_utils::ArkSerializationError,
     try_iter::{IndexIsOutOfBounds, InvalidPair},
@@ -31,6 +32,11 @@ pub enum BBSPlusError {
     SchnorrError(SchnorrError),
     MessageIndicesMustBeUniqueAndSorted(InvalidPair<usize>),
     MessageIndexIsOutOfBounds(IndexIsOutOfBounds),
+    /// Index of an uncommitted message is not less than the number of messages supported by the signature params
+    MessageIndexOutOfBounds {
+        index: usize,
+        max: usize,
+    },
     OTError(OTError),
     SenderIdCannotBeSameAsSelf(ParticipantId, ParticipantId),
     AlreadyHaveCommitmentFromParticipant(ParticipantId),
@@ -63,8 +69,243 @@ pub enum BBSPlusError {
     MissingResponsesProvidedForFullSchnorrProofVerification,
     NeedEitherPartialOrCompleteSchnorrResponse,
     CommonIndicesFoundInRevealedAndSkip,
+    /// The signature shares or the signature don't match the aggregation transcript
+    InvalidAggregationTranscript,
+    /// The randomness from Phase 1 at this index of the batch was already used to create a signature share
+    NonceReused(usize),
+    /// Index of the signature is not less than the batch size
+    SignatureIndexOutOfBatch(usize, u32),
+    /// The signature share of this participant was created with different OT extension parameters
+    IncompatibleOTEParams(ParticipantId),
+    /// The sum of `u` of the signature shares is 0 so the signature can't be created. Since honest
+    /// shares make this negligibly likely, this indicates a malicious signer and the protocol should be
+    /// aborted and the signers blamed
+    ZeroAggregateU,
+    /// No signature share was added to the aggregator
+    NoSignatureShares,
+    /// The number of signatures and the number of message sets being verified differ
+    UnequalNoOfSignaturesAndMessageSets(usize, usize),
+    /// Only this many signature shares, less than the number of signers of the session, have the same `e`, `s` and
+    /// OT extension parameters
+    NotEnoughConsistentShares(usize, ParticipantId),
+    /// The multiplication phase outputs being merged have different entries for this participant
+    ConflictingMultiplicationOutput(ParticipantId),
+    /// The multiplication phase outputs being merged were created with different OT extension parameters
+    MismatchedOTEParams,
+    /// The multiplication phase outputs of 2 signers don't add up to the product of their masked arguments
+    /// at this index of the batch
+    InconsistentProduct {
+        with: ParticipantId,
+        index: usize,
+    },
+    /// The salt size in bytes is less than `MIN_SALT_SIZE`
+    SaltTooSmall(usize),
+    /// The public key share of this participant is 0 or not in the prime order subgroup
+    InvalidPublicKeyShare(ParticipantId),
 }
 
+impl Display for BBSPlusError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CannotInvert0 => write!(f, "cannot invert 0"),
+            Self::NoMessageToSign => write!(f, "no message to sign"),
+            Self::MessageCountIncompatibleWithSigParams(given, supported) => write!(
+                f,
+                "got {} messages but the signature params support {}",
+                given, supported
+            ),
+            Self::ZeroSignature => write!(f, "signature's A is 0"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::PairingCheckFailed => write!(
+                f,
+                "pairing check failed during verification of proof of knowledge of signature"
+            ),
+            Self::FirstSchnorrVerificationFailed => write!(
+                f,
+                "1st schnorr proof failed during verification of proof of knowledge of signature"
+            ),
+            Self::SecondSchnorrVerificationFailed => write!(
+                f,
+                "2nd schnorr proof failed during verification of proof of knowledge of signature"
+            ),
+            Self::InvalidMsgIdxForResponse(idx) => {
+                write!(f, "no response for message at index {}", idx)
+            }
+            Self::Serialization(e) => write!(f, "serialization error: {}", e),
+            Self::SchnorrError(e) => write!(f, "schnorr error: {:?}", e),
+            Self::MessageIndicesMustBeUniqueAndSorted(InvalidPair(prev, cur)) => write!(
+                f,
+                "message indices must be unique and sorted but {} is followed by {}",
+                prev, cur
+            ),
+            Self::MessageIndexIsOutOfBounds(IndexIsOutOfBounds { index, length }) => write!(
+                f,
+                "message index {} is out of bounds for {} messages",
+                index, length
+            ),
+            Self::MessageIndexOutOfBounds { index, max } => write!(
+                f,
+                "message index {} is not less than the supported message count {}",
+                index, max
+            ),
+            Self::OTError(e) => write!(f, "OT error: {:?}", e),
+            Self::SenderIdCannotBeSameAsSelf(sender, self_id) => write!(
+                f,
+                "sender id {} cannot be same as own id {}",
+                sender, self_id
+            ),
+            Self::AlreadyHaveCommitmentFromParticipant(id) => {
+                write!(f, "already have commitment from participant {}", id)
+            }
+            Self::MissingCommitmentFromParticipant(id) => {
+                write!(f, "missing commitment from participant {}", id)
+            }
+            Self::IncorrectNoOfCommitments(expected, found) => write!(
+                f,
+                "incorrect number of commitments: expected {} but found {}",
+                expected, found
+            ),
+            Self::MissingSharesFromParticipant(id) => {
+                write!(f, "missing shares from participant {}", id)
+            }
+            Self::AlreadyHaveSharesFromParticipant(id) => {
+                write!(f, "already have shares from participant {}", id)
+            }
+            Self::IncorrectNoOfShares(expected, found) => write!(
+                f,
+                "incorrect number of shares: expected {} but found {}",
+                expected, found
+            ),
+            Self::IncorrectCommitment => write!(f, "incorrect commitment"),
+            Self::UnexpectedParticipant(id) => write!(f, "unexpected participant {}", id),
+            Self::MissingOTReceiverFor(id) => {
+                write!(f, "missing OT receiver for participant {}", id)
+            }
+            Self::MissingOTSenderFor(id) => write!(f, "missing OT sender for participant {}", id),
+            Self::NotAMultiplicationParty2(id) => {
+                write!(f, "participant {} is not a multiplication party 2", id)
+            }
+            Self::NotAMultiplicationParty1(id) => {
+                write!(f, "participant {} is not a multiplication party 1", id)
+            }
+            Self::UnexpectedMultiplicationParty1(id) => {
+                write!(f, "unexpected multiplication party 1 {}", id)
+            }
+            Self::UnexpectedMultiplicationParty2(id) => {
+                write!(f, "unexpected multiplication party 2 {}", id)
+            }
+            Self::IncorrectEByParticipant(id) => write!(f, "incorrect e by participant {}", id),
+            Self::IncorrectSByParticipant(id) => write!(f, "incorrect s by participant {}", id),
+            Self::ParticipantCannotBePresentInOthers(id) => write!(
+                f,
+                "participant {} cannot be present in the list of other participants",
+                id
+            ),
+            Self::NotABaseOTSender(id) => write!(f, "participant {} is not a base OT sender", id),
+            Self::NotABaseOTReceiver(id) => {
+                write!(f, "participant {} is not a base OT receiver", id)
+            }
+            Self::AlreadyHaveSenderPubkeyFrom(id) => {
+                write!(f, "already have sender public key from participant {}", id)
+            }
+            Self::AlreadyHaveReceiverPubkeyFrom(id) => {
+                write!(f, "already have receiver public key from participant {}", id)
+            }
+            Self::ReceiverNotReadyForChallengeFrom(id) => write!(
+                f,
+                "receiver not ready for challenge from participant {}",
+                id
+            ),
+            Self::AlreadyHaveChallengesFrom(id) => {
+                write!(f, "already have challenges from participant {}", id)
+            }
+            Self::SenderEitherNotReadyForResponseOrAlreadySentIt(id) => write!(
+                f,
+                "sender either not ready for response from participant {} or already sent it",
+                id
+            ),
+            Self::ReceiverEitherNotReadyForHashedKeysOrAlreadyVerifiedIt(id) => write!(
+                f,
+                "receiver either not ready for hashed keys from participant {} or already verified it",
+                id
+            ),
+            Self::SSError(e) => write!(f, "secret sharing error: {:?}", e),
+            Self::MissingResponsesNeededForPartialSchnorrProofVerification => write!(
+                f,
+                "missing responses needed for partial schnorr proof verification"
+            ),
+            Self::MissingResponsesProvidedForFullSchnorrProofVerification => write!(
+                f,
+                "responses for revealed messages provided for full schnorr proof verification"
+            ),
+            Self::NeedEitherPartialOrCompleteSchnorrResponse => {
+                write!(f, "need either partial or complete schnorr response")
+            }
+            Self::CommonIndicesFoundInRevealedAndSkip => {
+                write!(f, "common indices found in revealed and skipped indices")
+            }
+            Self::InvalidAggregationTranscript => write!(
+                f,
+                "signature shares or signature don't match the aggregation transcript"
+            ),
+            Self::NonceReused(idx) => write!(
+                f,
+                "randomness at index {} of the batch was already used to create a signature share",
+                idx
+            ),
+            Self::SignatureIndexOutOfBatch(idx, batch_size) => write!(
+                f,
+                "signature index {} is not less than the batch size {}",
+                idx, batch_size
+            ),
+            Self::IncompatibleOTEParams(id) => write!(
+                f,
+                "signature share of participant {} was created with different OT extension parameters",
+                id
+            ),
+            Self::ZeroAggregateU => write!(
+                f,
+                "sum of u of the signature shares is 0, a signer is malicious"
+            ),
+            Self::NoSignatureShares => write!(f, "no signature shares to aggregate"),
+            Self::UnequalNoOfSignaturesAndMessageSets(sigs, msgs) => write!(
+                f,
+                "got {} signatures but {} message sets",
+                sigs, msgs
+            ),
+            Self::NotEnoughConsistentShares(found, signers) => write!(
+                f,
+                "only {} consistent signature shares but the session has {} signers",
+                found, signers
+            ),
+            Self::ConflictingMultiplicationOutput(id) => write!(
+                f,
+                "multiplication outputs being merged have different entries for participant {}",
+                id
+            ),
+            Self::MismatchedOTEParams => write!(
+                f,
+                "multiplication outputs being merged were created with different OT extension parameters"
+            ),
+            Self::InconsistentProduct { with, index } => write!(
+                f,
+                "multiplication output with participant {} is inconsistent at index {}",
+                with, index
+            ),
+            Self::SaltTooSmall(size) => write!(
+                f,
+                "salt of {} bytes is smaller than the minimum of {} bytes",
+                size, MIN_SALT_SIZE
+            ),
+            Self::InvalidPublicKeyShare(id) => {
+                write!(f, "invalid public key share of participant {}", id)
+            }
+        }
+    }
+}
+
+impl core::error::Error for BBSPlusError {}
+
 impl From<SchnorrError> for BBSPlusError {
     fn from(e: SchnorrError) -> Self {
         Self::SchnorrError(e)
@@ -100,3 +341,25 @@ impl From<SSError> for BBSPlusError {
         Self::SSError(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::string::ToString;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            BBSPlusError::IncorrectEByParticipant(3).to_string(),
+            "incorrect e by participant 3"
+        );
+        assert_eq!(
+            BBSPlusError::NotEnoughConsistentShares(2, 4).to_string(),
+            "only 2 consistent signature shares but the session has 4 signers"
+        );
+        assert_eq!(
+            BBSPlusError::MessageCountIncompatibleWithSigParams(5, 4).to_string(),
+            "got 5 messages but the signature params support 4"
+        );
+    }
+}
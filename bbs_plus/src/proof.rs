@@ -916,7 +916,13 @@ mod tests {
                     .get_resp_for_message(*i, &revealed_indices)
                     .unwrap()
                 );
-                assert!(proof_3.get_resp_for_message(*i, &revealed_indices).is_err())
+                // The partial proof has no response for this message and the Schnorr error surfaces as a BBS+ error
+                assert!(matches!(
+                    proof_3.get_resp_for_message(*i, &revealed_indices),
+                    Err(BBSPlusError::SchnorrError(
+                        SchnorrError::MissingResponseAtIndex(_)
+                    ))
+                ));
             }
 
             let missing_resps = proof_1.get_responses(&same_msg_idx, &revealed_indices).unwrap();
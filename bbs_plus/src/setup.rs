@@ -49,9 +49,9 @@
 
 use crate::error::BBSPlusError;
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{cfg_iter, fmt::Debug, rand::RngCore, vec::Vec, UniformRand};
+use ark_std::{cfg_iter, collections::BTreeSet, fmt::Debug, rand::RngCore, vec::Vec, UniformRand};
 use digest::{Digest, DynDigest};
 
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -70,6 +70,8 @@ use dock_crypto_utils::{
     try_iter::CheckLeft,
 };
 use itertools::process_results;
+use oblivious_transfer_protocols::ParticipantId;
+use secret_sharing_and_dkg::common::lagrange_basis_at_0_for_all;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -347,10 +349,15 @@ macro_rules! impl_public_key {
         {
             impl_public_key_generation!(generate_using_secret_key, $params);
 
-            /// Public key shouldn't be 0. A verifier on receiving this must first check that its
+            /// Public key shouldn't be 0 and must be in the prime order subgroup, which matters when it was
+            /// deserialized without validation. A verifier on receiving this must first check that its
             /// valid and only then use it for any signature or proof of knowledge of signature verification.
             pub fn is_valid(&self) -> bool {
                 !self.0.is_zero()
+                    && self
+                        .0
+                        .mul_bigint(<E::ScalarField as PrimeField>::MODULUS)
+                        .is_zero()
             }
         }
     };
@@ -571,6 +578,34 @@ impl<E: Pairing> PublicKeyG2<E> {
         generate_using_secret_key_and_bbs23_params,
         SignatureParams23G1
     );
+
+    /// Generate a signer's share of the threshold public key from its share of the secret key. This is
+    /// `g2 * sk_i` where `sk_i` is the signer's share of the secret key.
+    pub fn generate_share_using_secret_key_share(
+        share: &SecretKey<E::ScalarField>,
+        params: &SignatureParamsG1<E>,
+    ) -> Self {
+        Self::generate_using_secret_key(share, params)
+    }
+
+    /// Combine the public key shares of at least `threshold` signers to get the threshold public key.
+    /// Each share is multiplied by its Lagrange coefficient and the results are added, i.e. Lagrange
+    /// interpolation in the exponent. Each share is tagged with the id of the signer it belongs to.
+    /// Returns `BBSPlusError::InvalidPublicKeyShare` if any share isn't valid as per `Self::is_valid`.
+    pub fn combine_shares(shares: Vec<(ParticipantId, Self)>) -> Result<Self, BBSPlusError> {
+        let mut ids = BTreeSet::new();
+        for (id, pk) in &shares {
+            if !ids.insert(*id) {
+                return Err(BBSPlusError::AlreadyHaveSharesFromParticipant(*id));
+            }
+            if !pk.is_valid() {
+                return Err(BBSPlusError::InvalidPublicKeyShare(*id));
+            }
+        }
+        let (ids, pks): (Vec<_>, Vec<_>) = shares.into_iter().map(|(id, pk)| (id, pk.0)).unzip();
+        let lcs = lagrange_basis_at_0_for_all::<E::ScalarField>(ids)?;
+        Ok(Self(E::G2::msm_unchecked(&pks, &lcs).into_affine()))
+    }
 }
 
 impl<E: Pairing> KeypairG2<E> {
@@ -594,6 +629,7 @@ mod tests {
         compute_random_oracle_challenge,
         discrete_log::{PokDiscreteLog, PokDiscreteLogProtocol},
     };
+    use secret_sharing_and_dkg::shamir_ss::deal_random_secret;
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
 
@@ -769,4 +805,86 @@ mod tests {
         check!(G2Affine, PublicKeyG2, SignatureParamsG1);
         check!(G1Affine, PublicKeyG1, SignatureParamsG2);
     }
+
+    #[test]
+    fn combine_public_key_shares() {
+        use ark_bls12_381::{Fq2, G2Affine};
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let params = SignatureParamsG1::<Bls12_381>::new::<Blake2b512>("test".as_bytes(), 5);
+        let threshold = 3;
+        let total = 5;
+        let (secret, shares, _) = deal_random_secret::<_, Fr>(&mut rng, threshold, total).unwrap();
+        let pk = PublicKeyG2::<Bls12_381>::generate_using_secret_key(&SecretKey(secret), &params);
+
+        let pk_shares = shares
+            .0
+            .iter()
+            .map(|s| {
+                (
+                    s.id,
+                    PublicKeyG2::generate_share_using_secret_key_share(
+                        &SecretKey(s.share),
+                        &params,
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Any `threshold` shares give the same public key
+        assert_eq!(
+            PublicKeyG2::combine_shares(pk_shares[0..3].to_vec()).unwrap(),
+            pk
+        );
+        assert_eq!(
+            PublicKeyG2::combine_shares(pk_shares[2..5].to_vec()).unwrap(),
+            pk
+        );
+        assert_eq!(
+            PublicKeyG2::combine_shares(vec![
+                pk_shares[4].clone(),
+                pk_shares[0].clone(),
+                pk_shares[2].clone()
+            ])
+            .unwrap(),
+            pk
+        );
+
+        // Fewer than `threshold` shares don't
+        assert_ne!(
+            PublicKeyG2::combine_shares(pk_shares[0..2].to_vec()).unwrap(),
+            pk
+        );
+
+        // Duplicate shares are rejected
+        assert!(matches!(
+            PublicKeyG2::combine_shares(vec![
+                pk_shares[0].clone(),
+                pk_shares[1].clone(),
+                pk_shares[0].clone()
+            ]),
+            Err(BBSPlusError::AlreadyHaveSharesFromParticipant(1))
+        ));
+
+        // Shares that are 0 or not in the prime order subgroup are rejected
+        let mut zero_share = pk_shares[0..3].to_vec();
+        zero_share[1].1 = PublicKeyG2(G2Affine::zero());
+        assert!(matches!(
+            PublicKeyG2::combine_shares(zero_share),
+            Err(BBSPlusError::InvalidPublicKeyShare(2))
+        ));
+        let not_in_subgroup = (1u64..)
+            .find_map(|x| {
+                G2Affine::get_point_from_x_unchecked(Fq2::from(x), false)
+                    .filter(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            })
+            .unwrap();
+        assert!(!PublicKeyG2::<Bls12_381>(not_in_subgroup).is_valid());
+        let mut bad_share = pk_shares[0..3].to_vec();
+        bad_share[2].1 = PublicKeyG2(not_in_subgroup);
+        assert!(matches!(
+            PublicKeyG2::combine_shares(bad_share),
+            Err(BBSPlusError::InvalidPublicKeyShare(3))
+        ));
+    }
 }
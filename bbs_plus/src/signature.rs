@@ -83,7 +83,7 @@
 //! ```
 
 use crate::error::BBSPlusError;
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group, VariableBaseMSM};
 use ark_ff::{fields::Field, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
@@ -293,6 +293,53 @@ impl<E: Pairing> SignatureG1<E> {
         }
         Ok(())
     }
+
+    /// Verify several signatures under the same public key where `sigs[i]` is the signature on
+    /// `messages_per_sig[i]`. The pairing checks of all signatures are combined with random `r_i` into the single
+    /// check `e(sum(r_i*A_i), pk)*e(sum(r_i*(A_i*e_i - b_i)), g2) == 1` so only 2 pairings are computed. Returns
+    /// `BBSPlusError::InvalidSignature` if any signature is invalid without telling which one.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        sigs: &[Self],
+        messages_per_sig: &[Vec<E::ScalarField>],
+        pk: impl Into<PreparedPublicKeyG2<E>>,
+        params: impl Into<PreparedSignatureParamsG1<E>>,
+    ) -> Result<(), BBSPlusError> {
+        expect_equality!(
+            sigs.len(),
+            messages_per_sig.len(),
+            BBSPlusError::UnequalNoOfSignaturesAndMessageSets
+        );
+        let params = params.into();
+        let mut As = Vec::with_capacity(sigs.len());
+        let mut bs = Vec::with_capacity(sigs.len());
+        let mut rs = Vec::with_capacity(sigs.len());
+        let mut r_es = Vec::with_capacity(sigs.len());
+        for (sig, messages) in sigs.iter().zip(messages_per_sig) {
+            bs.push(sig.pre_verify(messages, &params)?);
+            let r = E::ScalarField::rand(rng);
+            As.push(sig.A);
+            r_es.push(r * sig.e);
+            rs.push(r);
+        }
+        let sum_A = E::G1::msm_unchecked(&As, &rs);
+        // sum(r_i*A_i*e_i) - sum(r_i*b_i)
+        As.append(&mut E::G1::normalize_batch(&bs));
+        r_es.extend(rs.into_iter().map(|r| -r));
+        let sum_Aeb = E::G1::msm_unchecked(&As, &r_es);
+        if !E::multi_pairing(
+            [
+                E::G1Prepared::from(sum_A.into_affine()),
+                E::G1Prepared::from(sum_Aeb.into_affine()),
+            ],
+            [pk.into().0, params.g2],
+        )
+        .is_zero()
+        {
+            return Err(BBSPlusError::InvalidSignature);
+        }
+        Ok(())
+    }
 }
 
 impl<E: Pairing> SignatureG2<E> {
@@ -482,4 +529,70 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn batch_signature_verification() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 5;
+        let count = 10;
+        let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+        let keypair = KeypairG2::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let messages_per_sig = (0..count)
+            .map(|_| {
+                (0..message_count)
+                    .map(|_| Fr::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let sigs = messages_per_sig
+            .iter()
+            .map(|m| SignatureG1::new(&mut rng, m, &keypair.secret_key, &params).unwrap())
+            .collect::<Vec<_>>();
+
+        SignatureG1::verify_batch(
+            &mut rng,
+            &sigs,
+            &messages_per_sig,
+            keypair.public_key.clone(),
+            params.clone(),
+        )
+        .unwrap();
+
+        // One signature on different messages
+        let mut wrong_messages = messages_per_sig.clone();
+        wrong_messages[4][2] = Fr::rand(&mut rng);
+        assert!(matches!(
+            SignatureG1::verify_batch(
+                &mut rng,
+                &sigs,
+                &wrong_messages,
+                keypair.public_key.clone(),
+                params.clone(),
+            ),
+            Err(BBSPlusError::InvalidSignature)
+        ));
+
+        // Signatures swapped
+        let mut swapped = sigs.clone();
+        swapped.swap(1, 2);
+        assert!(SignatureG1::verify_batch(
+            &mut rng,
+            &swapped,
+            &messages_per_sig,
+            keypair.public_key.clone(),
+            params.clone(),
+        )
+        .is_err());
+
+        assert!(matches!(
+            SignatureG1::verify_batch(
+                &mut rng,
+                &sigs[1..],
+                &messages_per_sig,
+                keypair.public_key.clone(),
+                params.clone(),
+            ),
+            Err(BBSPlusError::UnequalNoOfSignaturesAndMessageSets(9, 10))
+        ));
+    }
 }
@@ -0,0 +1,191 @@
+//! Distributed key generation for threshold BBS+ with `SignatureParamsG1` so that the signers don't need a trusted
+//! dealer.
+//! Each of the `total` signers secret-shares a random secret using Feldman VSS in group G2 with the generator `g2` of
+//! the signature params and sends the shares and the commitments to the coefficients to the other signers. The signing
+//! key is the sum of these secrets and is never known to any signer. Each signer's share of the signing key is the sum
+//! of the shares it received and the public key is the sum of the commitments to the secrets.
+//!
+//! Uses `feldman_dvss_dkg` from `secret_sharing_and_dkg` so a malicious signer can bias the distribution of the
+//! public key by choosing its secret after seeing the commitments of others. Use `gennaro_dkg` from that crate if this
+//! is a concern.
+
+use crate::{
+    error::BBSPlusError,
+    setup::{PublicKeyG2, SecretKey, SignatureParamsG1},
+    threshold::ParticipantId,
+};
+use ark_ec::pairing::Pairing;
+use ark_std::rand::RngCore;
+use secret_sharing_and_dkg::{
+    common::{CommitmentToCoefficients, Share, ShareId, Shares},
+    feldman_dvss_dkg::SharesAccumulator,
+    feldman_vss::deal_random_secret,
+};
+
+/// Output of `KeygenParticipant::init`: the participant, the shares of its secret for all signers and the
+/// commitments to the coefficients
+pub type KeygenInitOutput<E> = (
+    KeygenParticipant<E>,
+    Shares<<E as Pairing>::ScalarField>,
+    CommitmentToCoefficients<<E as Pairing>::G2Affine>,
+);
+
+/// A signer taking part in the distributed key generation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeygenParticipant<E: Pairing> {
+    accumulator: SharesAccumulator<E::G2Affine>,
+    /// Total number of signers, each of which must send its share
+    total: ShareId,
+}
+
+impl<E: Pairing> KeygenParticipant<E> {
+    /// Create a random secret and its shares for all `total` signers. Returns the shares and the commitments to the
+    /// coefficients which are sent to the other signers, i.e. signer `j` is sent `shares.0[j - 1]`.
+    pub fn init<R: RngCore>(
+        rng: &mut R,
+        id: ParticipantId,
+        threshold: ShareId,
+        total: ShareId,
+        params: &SignatureParamsG1<E>,
+    ) -> Result<KeygenInitOutput<E>, BBSPlusError> {
+        if id == 0 || id > total {
+            return Err(BBSPlusError::UnexpectedParticipant(id));
+        }
+        let (_, shares, commitments, _) = deal_random_secret(rng, threshold, total, &params.g2)?;
+        let mut accumulator = SharesAccumulator::new(id, threshold);
+        accumulator.add_self_share(shares.0[id as usize - 1].clone(), commitments.clone());
+        Ok((Self { accumulator, total }, shares, commitments))
+    }
+
+    /// Process the share and commitments sent by signer `sender_id`. The share must be sent only to this signer
+    /// whereas the commitments are sent to all. Returns an error if the share doesn't match the commitments.
+    pub fn receive_share(
+        &mut self,
+        sender_id: ParticipantId,
+        share: Share<E::ScalarField>,
+        commitments: CommitmentToCoefficients<E::G2Affine>,
+        params: &SignatureParamsG1<E>,
+    ) -> Result<(), BBSPlusError> {
+        self.accumulator
+            .add_received_share(sender_id, share, commitments, &params.g2)?;
+        Ok(())
+    }
+
+    /// Called once shares from all other signers have been received. Returns this signer's share of the signing key
+    /// and the public key. Returns `BBSPlusError::MissingSharesFromParticipant` if the share of any of the `total`
+    /// signers wasn't received as then the key share and public key would differ from the ones of other signers.
+    pub fn finish(
+        self,
+        params: &SignatureParamsG1<E>,
+    ) -> Result<(SecretKey<E::ScalarField>, PublicKeyG2<E>), BBSPlusError> {
+        if let Some(id) = (1..=self.total).find(|i| !self.accumulator.shares.contains_key(i)) {
+            return Err(BBSPlusError::MissingSharesFromParticipant(id));
+        }
+        let (share, _, pk) = self.accumulator.finalize(&params.g2)?;
+        Ok((SecretKey(share.share), PublicKeyG2(pk)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold::threshold_bbs_plus::{
+        tests::do_base_ot_and_phase1_and_phase2, BBSPlusSignatureShare,
+    };
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+
+    #[test]
+    fn keygen_and_sign() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let total_signers = 5;
+        let message_count = 4;
+        let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+
+        let mut participants = vec![];
+        let mut dealt = vec![];
+        for i in 1..=total_signers {
+            let (participant, shares, commitments) =
+                KeygenParticipant::init(&mut rng, i, threshold_signers, total_signers, &params)
+                    .unwrap();
+            participants.push(participant);
+            dealt.push((i, shares, commitments));
+        }
+
+        // A share that doesn't match the commitments is rejected
+        let mut bad_share = dealt[1].1 .0[0].clone();
+        bad_share.share += Fr::rand(&mut rng);
+        assert!(participants[0]
+            .receive_share(2, bad_share, dealt[1].2.clone(), &params)
+            .is_err());
+
+        // Finishing without the shares of all signers fails
+        assert!(matches!(
+            participants[0].clone().finish(&params),
+            Err(BBSPlusError::MissingSharesFromParticipant(2))
+        ));
+
+        for (sender_id, shares, commitments) in &dealt {
+            for (j, participant) in participants.iter_mut().enumerate() {
+                if j + 1 != *sender_id as usize {
+                    participant
+                        .receive_share(
+                            *sender_id,
+                            shares.0[j].clone(),
+                            commitments.clone(),
+                            &params,
+                        )
+                        .unwrap();
+                }
+            }
+        }
+
+        // Missing the share of one signer fails even with more than a threshold number of shares
+        let mut partial = participants[0].clone();
+        partial.accumulator.shares.remove(&total_signers);
+        assert!(matches!(
+            partial.finish(&params),
+            Err(BBSPlusError::MissingSharesFromParticipant(id)) if id == total_signers
+        ));
+
+        let mut sk_shares = vec![];
+        let mut pks = vec![];
+        for participant in participants {
+            let (sk_share, pk) = participant.finish(&params).unwrap();
+            sk_shares.push(sk_share.0);
+            pks.push(pk);
+        }
+        // All signers get the same public key
+        assert!(pks.iter().all(|pk| *pk == pks[0]));
+        let public_key = pks.remove(0);
+
+        let (mut phase1_outs, phase2_outs) = do_base_ot_and_phase1_and_phase2(
+            &mut rng,
+            threshold_signers,
+            total_signers,
+            1,
+            &sk_shares,
+        );
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shares = (0..threshold_signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new(
+                    &messages,
+                    0,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let sig = BBSPlusSignatureShare::aggregate(shares).unwrap();
+        sig.verify(&messages, public_key, params).unwrap();
+    }
+}
@@ -12,10 +12,11 @@
 //! in threshold signing, phase 1 and 2 support batching meaning that to generate `n` signatures only a single execution
 //! of phase 1 and 2 needs to done, although with larger inputs. Then `n` executions of phase 3 are done to generate
 //! the signature.
-//! Also, its assumed that parties have done the DKG as well as the base OT and stored their results before starting phase 1.
+//! Also, its assumed that parties have done the DKG (see `dkg`) as well as the base OT and stored their results before starting phase 1.
 //! Both BBS and BBS+ implementations share the same multiplication phase and the base OT phase but their phase 1 is slightly
 //! less expensive as BBS+ needs 2 random fields elements but BBS needs only 1.
 
+pub mod dkg;
 pub mod multiplication_phase;
 pub mod randomness_generation_phase;
 pub mod threshold_bbs;
@@ -25,8 +25,114 @@ pub struct Phase2<F: PrimeField, const KAPPA: u16, const STATISTICAL_SECURITY_PA
     pub MultiplicationParty<F, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
 );
 
+/// Output of the multiplication phase along with the parameters of the OT extension used in it
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
-pub struct Phase2Output<F: PrimeField>(pub MultiplicationPartyOutput<F>);
+pub struct Phase2Output<F: PrimeField>(pub MultiplicationPartyOutput<F>, pub OTEParamsRecord);
+
+/// The parameters `KAPPA` and `STATISTICAL_SECURITY_PARAMETER` of the OT extension used in the multiplication
+/// phase. These are recorded in the signature shares so that shares created with different parameters
+/// are not aggregated together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct OTEParamsRecord {
+    pub kappa: u16,
+    pub statistical_security_parameter: u16,
+}
+
+impl<F: PrimeField> Phase2Output<F> {
+    /// Merge the outputs of 2 runs of the multiplication phase by the same party, like when the multiplication with
+    /// a late joining signer is done in a separate run. Both runs must have used the masked arguments of the same
+    /// phase 1 output, else the merged output doesn't give a valid signature. Entries for the same participant must
+    /// be equal in both outputs, else `BBSPlusError::ConflictingMultiplicationOutput` is returned.
+    pub fn merge(self, other: Self) -> Result<Self, BBSPlusError> {
+        if self.1 != other.1 {
+            return Err(BBSPlusError::MismatchedOTEParams);
+        }
+        let mut output = self.0;
+        for (merged, entries) in [
+            (&mut output.z_A, other.0.z_A),
+            (&mut output.z_B, other.0.z_B),
+        ] {
+            for (id, entry) in entries {
+                match merged.get(&id) {
+                    Some(existing) if *existing != entry => {
+                        return Err(BBSPlusError::ConflictingMultiplicationOutput(id))
+                    }
+                    Some(_) => (),
+                    None => {
+                        merged.insert(id, entry);
+                    }
+                }
+            }
+        }
+        Ok(Self(output, self.1))
+    }
+
+    /// Check that this output and the output `other` of another signer contain additive shares of the
+    /// products of their masked arguments, i.e. `my_masked_key * their_masked_r` and `my_masked_r * their_masked_key`
+    /// for each item of the batch. `ids` is the pair of ids of this signer and the other signer. Returns
+    /// `BBSPlusError::InconsistentProduct` with the first index where a product doesn't match and
+    /// `BBSPlusError::UnexpectedParticipant` if the outputs don't have a multiplication between the 2 signers.
+    pub fn verify_against(
+        &self,
+        other: &Self,
+        ids: (ParticipantId, ParticipantId),
+        my_masked_key: &[F],
+        my_masked_r: &[F],
+        their_masked_key: &[F],
+        their_masked_r: &[F],
+    ) -> Result<(), BBSPlusError> {
+        let (my_id, their_id) = ids;
+        // The signer acting as Party1 has the share in `z_A` and the one acting as Party2 has it in `z_B`.
+        // The 1st vector of the shares is for Party1's signing key share and the 2nd for its `r`
+        let (z_A, z_B, key_1, r_1, key_2, r_2) =
+            match (self.0.z_A.get(&their_id), other.0.z_B.get(&my_id)) {
+                (Some(z_A), Some(z_B)) => (
+                    z_A,
+                    z_B,
+                    my_masked_key,
+                    my_masked_r,
+                    their_masked_key,
+                    their_masked_r,
+                ),
+                _ => match (other.0.z_A.get(&my_id), self.0.z_B.get(&their_id)) {
+                    (Some(z_A), Some(z_B)) => (
+                        z_A,
+                        z_B,
+                        their_masked_key,
+                        their_masked_r,
+                        my_masked_key,
+                        my_masked_r,
+                    ),
+                    _ => return Err(BBSPlusError::UnexpectedParticipant(their_id)),
+                },
+            };
+        // Indices beyond the shortest input can't be checked and are treated as inconsistent
+        let checkable = [
+            key_1.len(),
+            r_1.len(),
+            key_2.len(),
+            r_2.len(),
+            z_A.1.len(),
+            z_B.0.len(),
+            z_B.1.len(),
+        ]
+        .into_iter()
+        .min()
+        .unwrap();
+        for index in 0..z_A.0.len() {
+            let consistent = index < checkable
+                && z_A.0[index] + z_B.0[index] == key_1[index] * r_2[index]
+                && z_A.1[index] + z_B.1[index] == r_1[index] * key_2[index];
+            if !consistent {
+                return Err(BBSPlusError::InconsistentProduct {
+                    with: their_id,
+                    index,
+                });
+            }
+        }
+        Ok(())
+    }
+}
 
 impl<F: PrimeField, const KAPPA: u16, const STATISTICAL_SECURITY_PARAMETER: u16>
     Phase2<F, KAPPA, STATISTICAL_SECURITY_PARAMETER>
@@ -81,6 +187,12 @@ impl<F: PrimeField, const KAPPA: u16, const STATISTICAL_SECURITY_PARAMETER: u16>
     }
 
     pub fn finish(self) -> Phase2Output<F> {
-        Phase2Output(self.0.finish())
+        Phase2Output(
+            self.0.finish(),
+            OTEParamsRecord {
+                kappa: KAPPA,
+                statistical_security_parameter: STATISTICAL_SECURITY_PARAMETER,
+            },
+        )
     }
 }
@@ -2,10 +2,21 @@ use super::ParticipantId;
 use crate::{error::BBSPlusError, threshold::utils::compute_masked_arguments_to_multiply};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::vec::Vec;
+use ark_std::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use digest::DynDigest;
 use oblivious_transfer_protocols::{cointoss, zero_sharing};
 
+/// Minimum size in bytes of the salts used in the commitments of the coin-toss and zero sharing protocols. A
+/// smaller salt would let the committed shares be found by brute force.
+pub const MIN_SALT_SIZE: usize = 16;
+
+/// Shares with salts for the joint randomness protocol and for the zero sharing protocol sent by a party to another
+pub type SharesForOther<F, const SALT_SIZE: usize> =
+    (Vec<(F, [u8; SALT_SIZE])>, Vec<(F, [u8; SALT_SIZE])>);
+
 /// This is the first phase of the signing protocol where parties generate random values, jointly and
 /// individually including additive shares of 0.
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
@@ -39,6 +50,28 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
             .clone()
     }
 
+    /// Get the shares to be sent to each of the `recipients` in a single call. Returns an error if this party isn't
+    /// running the zero sharing protocol with any of the recipients.
+    pub fn get_all_shares_for(
+        &self,
+        recipients: &BTreeSet<ParticipantId>,
+    ) -> Result<BTreeMap<ParticipantId, SharesForOther<F, SALT_SIZE>>, BBSPlusError> {
+        let shares = self.get_comm_shares_and_salts();
+        recipients
+            .iter()
+            .map(|id| {
+                let zero_shares = self
+                    .zero_sharing_protocol
+                    .cointoss_protocols
+                    .get(id)
+                    .ok_or(BBSPlusError::UnexpectedParticipant(*id))?
+                    .own_shares_and_salts
+                    .clone();
+                Ok((*id, (shares.clone(), zero_shares)))
+            })
+            .collect()
+    }
+
     /// Process received commitments for joint randomness and zero sharing protocol
     pub fn receive_commitment(
         &mut self,
@@ -66,6 +99,18 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
         Ok(())
     }
 
+    /// Process the shares received from several parties in a single call, i.e. the output of `get_all_shares_for`
+    /// of each sender for this party, keyed by the sender
+    pub fn receive_shares_bulk(
+        &mut self,
+        shares: BTreeMap<ParticipantId, SharesForOther<F, SALT_SIZE>>,
+    ) -> Result<(), BBSPlusError> {
+        for (sender_id, (shares, zero_shares)) in shares {
+            self.receive_shares(sender_id, shares, zero_shares)?;
+        }
+        Ok(())
+    }
+
     /// Computes joint randomness and masked arguments to multiply
     pub fn compute_randomness_and_arguments_for_multiplication<D: Default + DynDigest + Clone>(
         self,
@@ -13,8 +13,10 @@ use digest::DynDigest;
 use dock_crypto_utils::expect_equality;
 
 use crate::{
-    error::BBSPlusError, setup::SignatureParams23G1, signature_23::Signature23G1,
-    threshold::randomness_generation_phase::Phase1,
+    error::BBSPlusError,
+    setup::SignatureParams23G1,
+    signature_23::Signature23G1,
+    threshold::randomness_generation_phase::{Phase1, MIN_SALT_SIZE},
 };
 use dock_crypto_utils::signature::MultiMessageSignatureParams;
 use oblivious_transfer_protocols::{cointoss, zero_sharing, ParticipantId};
@@ -46,7 +48,7 @@ pub struct BBSSignatureShare<E: Pairing> {
 }
 
 impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
-    pub fn init_for_bbs<R: RngCore>(
+    pub fn init_for_bbs<R: RngCore + ?Sized>(
         rng: &mut R,
         batch_size: u32,
         id: ParticipantId,
@@ -60,6 +62,9 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
         ),
         BBSPlusError,
     > {
+        if SALT_SIZE < MIN_SALT_SIZE {
+            return Err(BBSPlusError::SaltTooSmall(SALT_SIZE));
+        }
         if others.contains(&id) {
             return Err(BBSPlusError::ParticipantCannotBePresentInOthers(id));
         }
@@ -166,6 +171,8 @@ impl<E: Pairing> BBSSignatureShare<E> {
         })
     }
 
+    /// Aggregate the signature shares to create the signature. Returns `BBSPlusError::ZeroAggregateU` if
+    /// the `u` of the shares sum to 0, which callers should treat as a reason to abort and blame the signers.
     pub fn aggregate(sig_shares: Vec<Self>) -> Result<Signature23G1<E>, BBSPlusError> {
         // TODO: Ensure correct threshold. Share should contain threshold and share id
         let mut sum_R = E::G1::zero();
@@ -182,7 +189,7 @@ impl<E: Pairing> BBSSignatureShare<E> {
             sum_u += share.u;
             sum_R += share.R;
         }
-        let A = sum_R * sum_u.inverse().unwrap();
+        let A = sum_R * sum_u.inverse().ok_or(BBSPlusError::ZeroAggregateU)?;
         Ok(Signature23G1 {
             A: A.into_affine(),
             e: expected_e,
@@ -425,4 +432,103 @@ pub mod tests {
         println!("Generating signature shares took {:?}", sig_shares_time);
         println!("Aggregating signature shares took {:?}", sig_aggr_time);
     }
+
+    #[test]
+    fn phase1_with_bulk_shares() {
+        let threshold_signers = 4;
+        let total_signers = 6;
+        let sig_batch_size = 2;
+        let threshold_party_set = (1..=threshold_signers).collect::<BTreeSet<_>>();
+        let (_, sk_shares) = trusted_party_keygen::<_, Fr>(
+            &mut StdRng::seed_from_u64(0u64),
+            threshold_signers,
+            total_signers,
+        );
+
+        // Initiate round-1 and process the commitments of others. Same seed gives the same randomness
+        let init = || {
+            let mut rng = StdRng::seed_from_u64(1u64);
+            let mut round1s = vec![];
+            let mut commitments = vec![];
+            let mut commitments_zero_share = vec![];
+            for i in 1..=threshold_signers {
+                let mut others = threshold_party_set.clone();
+                others.remove(&i);
+                let (round1, comm, comm_zero) = Phase1::<Fr, 256>::init_for_bbs(
+                    &mut rng,
+                    sig_batch_size,
+                    i,
+                    others,
+                    b"test".to_vec(),
+                )
+                .unwrap();
+                round1s.push(round1);
+                commitments.push(comm);
+                commitments_zero_share.push(comm_zero);
+            }
+            for i in 1..=threshold_signers {
+                for j in 1..=threshold_signers {
+                    if i != j {
+                        round1s[i as usize - 1]
+                            .receive_commitment(
+                                j,
+                                commitments[j as usize - 1].clone(),
+                                commitments_zero_share[j as usize - 1]
+                                    .get(&i)
+                                    .unwrap()
+                                    .clone(),
+                            )
+                            .unwrap();
+                    }
+                }
+            }
+            round1s
+        };
+
+        // Exchange shares one pair of signers at a time
+        let mut round1s = init();
+        for i in 1..=threshold_signers {
+            for j in 1..=threshold_signers {
+                if i != j {
+                    let share = round1s[j as usize - 1].get_comm_shares_and_salts();
+                    let zero_share = round1s[j as usize - 1]
+                        .get_comm_shares_and_salts_for_zero_sharing_protocol_with_other(&i);
+                    round1s[i as usize - 1]
+                        .receive_shares(j, share, zero_share)
+                        .unwrap();
+                }
+            }
+        }
+        let expected = round1s
+            .into_iter()
+            .enumerate()
+            .map(|(i, round1)| round1.finish_for_bbs::<Blake2b512>(&sk_shares[i]).unwrap())
+            .collect::<Vec<_>>();
+
+        // Each signer creates the shares for all others at once and receives the shares from all others at once
+        let mut round1s = init();
+        let mut received = (1..=threshold_signers)
+            .map(|_| BTreeMap::new())
+            .collect::<Vec<_>>();
+        for j in 1..=threshold_signers {
+            let mut others = threshold_party_set.clone();
+            others.remove(&j);
+            for (i, shares) in round1s[j as usize - 1].get_all_shares_for(&others).unwrap() {
+                received[i as usize - 1].insert(j, shares);
+            }
+        }
+        // Shares can't be created for a party not in the protocol
+        assert!(round1s[0]
+            .get_all_shares_for(&BTreeSet::from([threshold_signers + 1]))
+            .is_err());
+        for (round1, shares) in round1s.iter_mut().zip(received) {
+            round1.receive_shares_bulk(shares).unwrap();
+        }
+        let outputs = round1s
+            .into_iter()
+            .enumerate()
+            .map(|(i, round1)| round1.finish_for_bbs::<Blake2b512>(&sk_shares[i]).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, expected);
+    }
 }
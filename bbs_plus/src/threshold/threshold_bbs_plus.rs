@@ -1,26 +1,439 @@
-use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
-use ark_ff::{Field, PrimeField, Zero};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, Field, PrimeField, Zero};
 
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use ark_std::{
+    cfg_into_iter,
     collections::{BTreeMap, BTreeSet},
+    io::Read,
+    iter,
     rand::RngCore,
+    vec,
     vec::Vec,
 };
-use digest::DynDigest;
+use digest::{Digest, DynDigest};
 use dock_crypto_utils::expect_equality;
 use oblivious_transfer_protocols::{cointoss, zero_sharing, ParticipantId};
 
-use super::{multiplication_phase::Phase2Output, utils::compute_R_and_u};
+use super::{
+    multiplication_phase::{OTEParamsRecord, Phase2Output},
+    utils::compute_R_and_u,
+};
 use crate::{
-    error::BBSPlusError, setup::SignatureParamsG1, signature::SignatureG1,
-    threshold::randomness_generation_phase::Phase1,
+    error::BBSPlusError,
+    setup::{SignatureParamsG1, SignatureParamsG2},
+    signature::{SignatureG1, SignatureG2},
+    threshold::randomness_generation_phase::{Phase1, MIN_SALT_SIZE},
 };
 use dock_crypto_utils::signature::MultiMessageSignatureParams;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+macro_rules! impl_signature_share {
+    ($share: ident, $aggregator: ident, $group_affine: ident, $group_projective: ident, $params: ident, $sig: ident) => {
+        /// A share of the BBS+ signature created by one signer. A client will aggregate many such shares to
+        /// create the final signature.
+        /// Note that a single share cannot be checked on its own. Checking `R` needs the signer's share of the
+        /// randomness `r` but learning all shares of `r` lets the client compute the signing key from `u`. Moreover,
+        /// a malicious signer can pick any `r` and still send an incorrect `u`. Such shares are only caught when the
+        /// aggregated signature is verified.
+        /// A pairing check of `R` against the signer's public key share, like `e(R, pk_share) == e(X, g2)`, isn't
+        /// possible either since `R = r * (commitment + b)` doesn't involve the signing key share at all and the
+        /// only `X` satisfying it would be `R * sk_share` which needs the signing key share. The signing key share
+        /// only enters `u`, masked and mixed with the other signers' shares through the multiplication phase.
+        #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+        pub struct $share<E: Pairing> {
+            pub id: ParticipantId,
+            pub e: E::ScalarField,
+            pub s: E::ScalarField,
+            pub u: E::ScalarField,
+            pub R: E::$group_affine,
+            /// Parameters of the OT extension used in the multiplication phase
+            pub ote_params: OTEParamsRecord,
+        }
+
+        /// Aggregates BBS+ signature shares one at a time as they arrive rather than requiring all of them at once.
+        /// Each share is checked to have the same `e`, `s` and OT extension parameters as the first share.
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $aggregator<E: Pairing> {
+            sum_R: E::$group_projective,
+            sum_u: E::ScalarField,
+            /// `e`, `s` and OT extension parameters of the first share
+            expected: Option<(E::ScalarField, E::ScalarField, OTEParamsRecord)>,
+        }
+
+        impl<E: Pairing> $share<E> {
+            /// `sig_index_in_batch` is the index of this signature in batch and also in the Phase1 and Phase2 outputs.
+            /// The randomness of `phase1` at `sig_index_in_batch` is marked as used and an error is returned if it was
+            /// already used for another signature share.
+            pub fn new(
+                messages: &[E::ScalarField],
+                sig_index_in_batch: usize,
+                phase1: &mut Phase1Output<E::ScalarField>,
+                phase2: &Phase2Output<E::ScalarField>,
+                sig_params: &$params<E>,
+            ) -> Result<Self, BBSPlusError> {
+                if messages.is_empty() {
+                    return Err(BBSPlusError::NoMessageToSign);
+                }
+                expect_equality!(
+                    messages.len(),
+                    sig_params.supported_message_count(),
+                    BBSPlusError::MessageCountIncompatibleWithSigParams
+                );
+                // Create map of msg index (0-based) -> message
+                let msg_map: BTreeMap<usize, &E::ScalarField> =
+                    messages.iter().enumerate().map(|(i, e)| (i, e)).collect();
+                Self::new_with_committed_messages(
+                    &E::$group_affine::zero(),
+                    msg_map,
+                    sig_index_in_batch,
+                    phase1,
+                    phase2,
+                    sig_params,
+                )
+            }
+
+            /// `sig_index_in_batch` is the index of this signature in batch and also in the Phase1 and Phase2 outputs.
+            /// Returns an error if any index of `uncommitted_messages` is not less than the number of messages
+            /// supported by `sig_params` or if the randomness of `phase1` at `sig_index_in_batch` was already used.
+            pub fn new_with_committed_messages(
+                commitment: &E::$group_affine,
+                uncommitted_messages: BTreeMap<usize, &E::ScalarField>,
+                sig_index_in_batch: usize,
+                phase1: &mut Phase1Output<E::ScalarField>,
+                phase2: &Phase2Output<E::ScalarField>,
+                sig_params: &$params<E>,
+            ) -> Result<Self, BBSPlusError> {
+                let max = sig_params.supported_message_count();
+                if let Some(index) = uncommitted_messages.keys().find(|i| **i >= max) {
+                    return Err(BBSPlusError::MessageIndexOutOfBounds { index: *index, max });
+                }
+                if sig_index_in_batch >= phase1.batch_size as usize {
+                    return Err(BBSPlusError::SignatureIndexOutOfBatch(
+                        sig_index_in_batch,
+                        phase1.batch_size,
+                    ));
+                }
+                if !phase1.is_fresh(sig_index_in_batch) {
+                    return Err(BBSPlusError::NonceReused(sig_index_in_batch));
+                }
+                // Mark the randomness as used only once `b` is computed successfully
+                let b = sig_params.b(uncommitted_messages, &phase1.s[sig_index_in_batch])?;
+                phase1.consume(sig_index_in_batch)?;
+                let commitment_plus_b = b + commitment;
+                let (R, u) = compute_R_and_u(
+                    commitment_plus_b,
+                    &phase1.r[sig_index_in_batch],
+                    &phase1.e[sig_index_in_batch],
+                    &phase1.masked_rs[sig_index_in_batch],
+                    &phase1.masked_signing_key_shares[sig_index_in_batch],
+                    sig_index_in_batch as u32,
+                    phase2,
+                );
+                Ok(Self {
+                    id: phase1.id,
+                    e: phase1.e[sig_index_in_batch],
+                    s: phase1.s[sig_index_in_batch],
+                    u,
+                    R,
+                    ote_params: phase2.1,
+                })
+            }
+
+            /// Create shares for several signatures of the batch at once where `messages_per_sig[i]` are the messages
+            /// of the signature at index `i` of the batch. The bases for computing each `b` are extracted from
+            /// `sig_params` only once. Returns an error without marking any of the randomness of `phase1` as used
+            /// if the randomness at any of these indices was already used.
+            pub fn new_batch(
+                messages_per_sig: &[Vec<E::ScalarField>],
+                phase1: &mut Phase1Output<E::ScalarField>,
+                phase2: &Phase2Output<E::ScalarField>,
+                sig_params: &$params<E>,
+            ) -> Result<Vec<Self>, BBSPlusError> {
+                if messages_per_sig.len() > phase1.batch_size as usize {
+                    return Err(BBSPlusError::SignatureIndexOutOfBatch(
+                        messages_per_sig.len() - 1,
+                        phase1.batch_size,
+                    ));
+                }
+                for (i, messages) in messages_per_sig.iter().enumerate() {
+                    if messages.is_empty() {
+                        return Err(BBSPlusError::NoMessageToSign);
+                    }
+                    expect_equality!(
+                        messages.len(),
+                        sig_params.supported_message_count(),
+                        BBSPlusError::MessageCountIncompatibleWithSigParams
+                    );
+                    if !phase1.is_fresh(i) {
+                        return Err(BBSPlusError::NonceReused(i));
+                    }
+                }
+                // b = g1 + h_0 * s + sum(h_i * m_i)
+                let bases = sig_params
+                    .h
+                    .iter()
+                    .chain(iter::once(&sig_params.h_0))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let mut shares = Vec::with_capacity(messages_per_sig.len());
+                for (i, messages) in messages_per_sig.iter().enumerate() {
+                    phase1.consume(i)?;
+                    let scalars = messages
+                        .iter()
+                        .chain(iter::once(&phase1.s[i]))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let b = E::$group_projective::msm_unchecked(&bases, &scalars) + sig_params.g1;
+                    let (R, u) = compute_R_and_u(
+                        b,
+                        &phase1.r[i],
+                        &phase1.e[i],
+                        &phase1.masked_rs[i],
+                        &phase1.masked_signing_key_shares[i],
+                        i as u32,
+                        phase2,
+                    );
+                    shares.push(Self {
+                        id: phase1.id,
+                        e: phase1.e[i],
+                        s: phase1.s[i],
+                        u,
+                        R,
+                        ote_params: phase2.1,
+                    });
+                }
+                Ok(shares)
+            }
+
+            /// Serialize with `R` uncompressed which is larger but faster to deserialize than the compressed form
+            pub fn serialize_uncompressed_to_vec(&self) -> Result<Vec<u8>, BBSPlusError> {
+                let mut bytes = Vec::with_capacity(self.uncompressed_size());
+                self.serialize_uncompressed(&mut bytes)?;
+                Ok(bytes)
+            }
+
+            /// Deserialize a share serialized with `Self::serialize_uncompressed_to_vec`. `R` is still checked to
+            /// be in the correct subgroup.
+            pub fn deserialize_uncompressed_from<R: Read>(reader: R) -> Result<Self, BBSPlusError> {
+                Ok(Self::deserialize_uncompressed(reader)?)
+            }
+
+            /// Aggregate the signature shares to create the signature. Returns `BBSPlusError::ZeroAggregateU` if
+            /// the `u` of the shares sum to 0, which callers should treat as a reason to abort and blame the signers.
+            pub fn aggregate(sig_shares: Vec<Self>) -> Result<$sig<E>, BBSPlusError> {
+                // TODO: Ensure correct threshold. Share should contain threshold and share id
+                Self::aggregate_from_iter(sig_shares)
+            }
+
+            /// Same as `Self::aggregate` but also returns the ids of the signers whose shares were aggregated, so
+            /// the signing quorum can be recorded with the signature. Returns
+            /// `BBSPlusError::AlreadyHaveSharesFromParticipant` if a signer has more than 1 share.
+            pub fn aggregate_with_participants(
+                sig_shares: Vec<Self>,
+            ) -> Result<($sig<E>, BTreeSet<ParticipantId>), BBSPlusError> {
+                let mut participants = BTreeSet::new();
+                let mut aggregator = $aggregator::new();
+                for share in sig_shares {
+                    if !participants.insert(share.id) {
+                        return Err(BBSPlusError::AlreadyHaveSharesFromParticipant(share.id));
+                    }
+                    aggregator.add_share(share)?;
+                }
+                Ok((aggregator.finalize()?, participants))
+            }
+
+            /// Same as `Self::aggregate` but takes the shares lazily from an iterator so that all shares don't need
+            /// to be in memory at once. Returns an error on the first share whose `e`, `s` or OT extension parameters
+            /// differ from the ones of the first share.
+            pub fn aggregate_from_iter(
+                sig_shares: impl IntoIterator<Item = Self>,
+            ) -> Result<$sig<E>, BBSPlusError> {
+                let mut aggregator = $aggregator::new();
+                for share in sig_shares {
+                    aggregator.add_share(share)?;
+                }
+                aggregator.finalize()
+            }
+
+            /// Aggregate the largest group of shares having the same `e`, `s` and OT extension parameters if it has
+            /// a share from each of `signers`, the signers who ran phase 1 and 2 together, like given by
+            /// `Phase1Output::signers`. A subset of those gives an invalid signature. Returns the signature and the
+            /// ids of the discarded shares, which includes a share of a signer not in `signers` and a repeated share
+            /// of a signer. Returns `BBSPlusError::NotEnoughConsistentShares` if no such group exists. This discards
+            /// shares from other signing sessions but not a share with the correct `e` and `s` and an incorrect `u`
+            /// or `R`, so the signature must still be verified.
+            pub fn aggregate_best_effort(
+                sig_shares: Vec<Self>,
+                signers: &BTreeSet<ParticipantId>,
+            ) -> Result<($sig<E>, Vec<ParticipantId>), BBSPlusError> {
+                let expected = signers.len() as ParticipantId;
+                let mut groups: Vec<Vec<Self>> = Vec::new();
+                let mut discarded = Vec::new();
+                for share in sig_shares {
+                    if !signers.contains(&share.id) {
+                        discarded.push(share.id);
+                        continue;
+                    }
+                    match groups.iter_mut().find(|g| {
+                        g[0].e == share.e
+                            && g[0].s == share.s
+                            && g[0].ote_params == share.ote_params
+                    }) {
+                        Some(g) if g.iter().any(|s| s.id == share.id) => discarded.push(share.id),
+                        Some(g) => g.push(share),
+                        None => groups.push(vec![share]),
+                    }
+                }
+                let largest = (0..groups.len())
+                    .max_by_key(|i| (groups[*i].len(), core::cmp::Reverse(*i)))
+                    .ok_or(BBSPlusError::NotEnoughConsistentShares(0, expected))?;
+                let selected = groups.swap_remove(largest);
+                // The group has no repeated shares and only shares of `signers`
+                if selected.len() != signers.len() {
+                    return Err(BBSPlusError::NotEnoughConsistentShares(
+                        selected.len(),
+                        expected,
+                    ));
+                }
+                discarded.extend(groups.iter().flatten().map(|s| s.id));
+                discarded.sort_unstable();
+                Ok((Self::aggregate(selected)?, discarded))
+            }
+
+            /// Aggregate the signature shares but without computing `A`. Returns `(sum_R, sum_u, e, s)` where
+            /// `A = sum_R * sum_u^-1` so that the caller can finalize `A` or invert several `sum_u` together.
+            /// Returns `BBSPlusError::ZeroAggregateU` if the `u` of the shares sum to 0.
+            pub fn aggregate_parts(
+                sig_shares: Vec<Self>,
+            ) -> Result<
+                (
+                    E::$group_projective,
+                    E::ScalarField,
+                    E::ScalarField,
+                    E::ScalarField,
+                ),
+                BBSPlusError,
+            > {
+                let mut aggregator = $aggregator::new();
+                for share in sig_shares {
+                    aggregator.add_share(share)?;
+                }
+                aggregator.into_parts()
+            }
+
+            /// Aggregate the shares of several signatures where `shares_per_sig[i]` are the shares of the `i`-th
+            /// signature. Same as calling `aggregate` for each signature but the sums of `u` are inverted together
+            /// using a single field inversion.
+            pub fn aggregate_batch(
+                shares_per_sig: Vec<Vec<Self>>,
+            ) -> Result<Vec<$sig<E>>, BBSPlusError> {
+                let mut sum_Rs = Vec::with_capacity(shares_per_sig.len());
+                let mut sum_us = Vec::with_capacity(shares_per_sig.len());
+                let mut e_s = Vec::with_capacity(shares_per_sig.len());
+                for shares in shares_per_sig {
+                    let (sum_R, sum_u, e, s) = Self::aggregate_parts(shares)?;
+                    sum_Rs.push(sum_R);
+                    sum_us.push(sum_u);
+                    e_s.push((e, s));
+                }
+                batch_inversion(&mut sum_us);
+                let As = cfg_into_iter!(sum_Rs)
+                    .zip(cfg_into_iter!(sum_us))
+                    .map(|(sum_R, sum_u_inv)| sum_R * sum_u_inv)
+                    .collect::<Vec<_>>();
+                Ok(E::$group_projective::normalize_batch(&As)
+                    .into_iter()
+                    .zip(e_s)
+                    .map(|(A, (e, s))| $sig { A, e, s })
+                    .collect())
+            }
+        }
+
+        impl<E: Pairing> Default for $aggregator<E> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<E: Pairing> $aggregator<E> {
+            pub fn new() -> Self {
+                Self {
+                    sum_R: E::$group_projective::zero(),
+                    sum_u: E::ScalarField::zero(),
+                    expected: None,
+                }
+            }
+
+            /// Add a share to the aggregate. Returns an error if the share's `e`, `s` or OT extension parameters
+            /// differ from the ones of the shares added before.
+            pub fn add_share(&mut self, share: $share<E>) -> Result<(), BBSPlusError> {
+                match &self.expected {
+                    None => self.expected = Some((share.e, share.s, share.ote_params)),
+                    Some((e, s, ote_params)) => {
+                        if *e != share.e {
+                            return Err(BBSPlusError::IncorrectEByParticipant(share.id));
+                        }
+                        if *s != share.s {
+                            return Err(BBSPlusError::IncorrectSByParticipant(share.id));
+                        }
+                        if *ote_params != share.ote_params {
+                            return Err(BBSPlusError::IncompatibleOTEParams(share.id));
+                        }
+                    }
+                }
+                self.sum_u += share.u;
+                self.sum_R += share.R;
+                Ok(())
+            }
+
+            /// Create the signature from the shares added so far. Returns `BBSPlusError::ZeroAggregateU` if the
+            /// `u` of the shares sum to 0, which callers should treat as a reason to abort and blame the signers.
+            pub fn finalize(self) -> Result<$sig<E>, BBSPlusError> {
+                let (sum_R, sum_u, e, s) = self.into_parts()?;
+                Ok($sig {
+                    A: (sum_R * sum_u.inverse().unwrap()).into_affine(),
+                    e,
+                    s,
+                })
+            }
+
+            /// Return `(sum_R, sum_u, e, s)` of the shares added so far without computing `A`. Returns
+            /// `BBSPlusError::NoSignatureShares` if no share was added and `BBSPlusError::ZeroAggregateU` if the
+            /// `u` of the shares sum to 0.
+            pub fn into_parts(
+                self,
+            ) -> Result<
+                (
+                    E::$group_projective,
+                    E::ScalarField,
+                    E::ScalarField,
+                    E::ScalarField,
+                ),
+                BBSPlusError,
+            > {
+                let (e, s, _) = self.expected.ok_or(BBSPlusError::NoSignatureShares)?;
+                if self.sum_u.is_zero() {
+                    return Err(BBSPlusError::ZeroAggregateU);
+                }
+                Ok((self.sum_R, self.sum_u, e, s))
+            }
+        }
+    };
+}
+
 /// The length of vectors `r`, `e`, `s`, `masked_signing_key_shares`, `masked_rs` should
-/// be `batch_size` each item of the vector corresponds to 1 signature
-#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+/// be `batch_size` each item of the vector corresponds to 1 signature. Deserialization, even unchecked, returns
+/// an error if any of these vectors or `consumed` has a different length.
+///
+/// Each clone of this tracks the used randomness on its own and so does a copy serialized before creating a
+/// signature share. Creating signature shares from 2 such copies uses the same `(e, s, r)` for 2 signatures which
+/// leaks the signing key, so create shares from a single copy and persist it after each share.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize)]
 pub struct Phase1Output<F: PrimeField> {
     pub id: ParticipantId,
     pub batch_size: u32,
@@ -33,21 +446,122 @@ pub struct Phase1Output<F: PrimeField> {
     /// Additive shares of `r` masked by a random `beta`
     pub masked_rs: Vec<F>,
     pub others: Vec<ParticipantId>,
+    /// Whether the randomness `(e, s, r)` at an index has been used to create a signature share. The randomness
+    /// must not be used for more than one signature as doing so leaks the signing key. Only changed by
+    /// `Self::consume`.
+    pub(crate) consumed: Vec<bool>,
 }
 
-/// A share of the BBS+ signature created by one signer. A client will aggregate many such shares to
-/// create the final signature.
-#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
-pub struct BBSPlusSignatureShare<E: Pairing> {
-    pub id: ParticipantId,
-    pub e: E::ScalarField,
-    pub s: E::ScalarField,
-    pub u: E::ScalarField,
-    pub R: E::G1Affine,
+impl<F: PrimeField> Valid for Phase1Output<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.check_lengths()?;
+        self.r.check()?;
+        self.e.check()?;
+        self.s.check()?;
+        self.masked_signing_key_shares.check()?;
+        self.masked_rs.check()
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for Phase1Output<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let phase1 = Self {
+            id: ParticipantId::deserialize_with_mode(&mut reader, compress, validate)?,
+            batch_size: u32::deserialize_with_mode(&mut reader, compress, validate)?,
+            r: Vec::<F>::deserialize_with_mode(&mut reader, compress, validate)?,
+            e: Vec::<F>::deserialize_with_mode(&mut reader, compress, validate)?,
+            s: Vec::<F>::deserialize_with_mode(&mut reader, compress, validate)?,
+            masked_signing_key_shares: Vec::<F>::deserialize_with_mode(
+                &mut reader,
+                compress,
+                validate,
+            )?,
+            masked_rs: Vec::<F>::deserialize_with_mode(&mut reader, compress, validate)?,
+            others: Vec::<ParticipantId>::deserialize_with_mode(&mut reader, compress, validate)?,
+            consumed: Vec::<bool>::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+        // Lengths are checked even without validation as the rest of the code indexes these vectors
+        phase1.check_lengths()?;
+        Ok(phase1)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, F: PrimeField> arbitrary::Arbitrary<'a> for Phase1Output<F> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use schnorr_pok::fuzzing::arbitrary_field_element;
+        let id = u.arbitrary()?;
+        let batch_size = u.int_in_range(0..=16u32)?;
+        let mut elements = || {
+            (0..batch_size)
+                .map(|_| arbitrary_field_element(u))
+                .collect::<arbitrary::Result<Vec<F>>>()
+        };
+        let r = elements()?;
+        let e = elements()?;
+        let s = elements()?;
+        let masked_signing_key_shares = elements()?;
+        let masked_rs = elements()?;
+        let others = u.arbitrary()?;
+        let consumed = (0..batch_size)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<_>>()?;
+        Ok(Self {
+            id,
+            batch_size,
+            r,
+            e,
+            s,
+            masked_signing_key_shares,
+            masked_rs,
+            others,
+            consumed,
+        })
+    }
+}
+
+impl<F: PrimeField> Phase1Output<F> {
+    /// Ids of all the signers who ran phase 1 together, i.e. `others` and this signer's id
+    pub fn signers(&self) -> BTreeSet<ParticipantId> {
+        self.others
+            .iter()
+            .copied()
+            .chain(iter::once(self.id))
+            .collect()
+    }
+
+    /// Serialize in the uncompressed form. As this contains no group elements, this is the same as the compressed
+    /// form and exists so that all threshold signing messages can be serialized the same way.
+    pub fn serialize_uncompressed_to_vec(&self) -> Result<Vec<u8>, BBSPlusError> {
+        let mut bytes = Vec::with_capacity(self.uncompressed_size());
+        self.serialize_uncompressed(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize an output serialized with `Self::serialize_uncompressed_to_vec`
+    pub fn deserialize_uncompressed_from<R: Read>(reader: R) -> Result<Self, BBSPlusError> {
+        Ok(Self::deserialize_uncompressed(reader)?)
+    }
+}
+
+/// Record of an aggregation of BBS+ signature shares created by `BBSPlusSignatureShare::aggregate_with_transcript`.
+/// Contains the ids of the signers whose shares were aggregated, in the order of aggregation, and the hash
+/// of each signer's `(u, R)`. Given the shares, anyone can check using `Self::verify` that exactly those
+/// shares were aggregated into the signature.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregationTranscript {
+    pub participants: Vec<ParticipantId>,
+    pub share_hashes: Vec<Vec<u8>>,
 }
 
 impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
-    pub fn init_for_bbs_plus<R: RngCore>(
+    /// Start phase 1 for a batch of `batch_size` BBS+ signatures. `rng` can be a trait object, like
+    /// `&mut dyn RngCore` backed by a hardware RNG.
+    pub fn init_for_bbs_plus<R: RngCore + ?Sized>(
         rng: &mut R,
         batch_size: u32,
         id: ParticipantId,
@@ -61,6 +575,9 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
         ),
         BBSPlusError,
     > {
+        if SALT_SIZE < MIN_SALT_SIZE {
+            return Err(BBSPlusError::SaltTooSmall(SALT_SIZE));
+        }
         if others.contains(&id) {
             return Err(BBSPlusError::ParticipantCannotBePresentInOthers(id));
         }
@@ -107,96 +624,136 @@ impl<F: PrimeField, const SALT_SIZE: usize> Phase1<F, SALT_SIZE> {
             masked_signing_key_shares,
             masked_rs,
             others,
+            consumed: vec![false; batch_size as usize],
         })
     }
+
+    /// End phase 1 and return the output of this phase when creating signatures in group G2, i.e. with
+    /// `SignatureParamsG2`. The output of this phase does not depend on the group so this is the same as
+    /// `Self::finish_for_bbs_plus`.
+    pub fn finish_for_bbs_plus_g2<D: Default + DynDigest + Clone>(
+        self,
+        signing_key: &F,
+    ) -> Result<Phase1Output<F>, BBSPlusError> {
+        self.finish_for_bbs_plus::<D>(signing_key)
+    }
 }
 
+impl<F: PrimeField> Phase1Output<F> {
+    fn check_lengths(&self) -> Result<(), SerializationError> {
+        let batch_size = self.batch_size as usize;
+        if [
+            self.r.len(),
+            self.e.len(),
+            self.s.len(),
+            self.masked_signing_key_shares.len(),
+            self.masked_rs.len(),
+            self.consumed.len(),
+        ]
+        .iter()
+        .all(|l| *l == batch_size)
+        {
+            Ok(())
+        } else {
+            Err(SerializationError::InvalidData)
+        }
+    }
+
+    /// Returns true if the randomness at index `index` of the batch hasn't been used to create a signature share yet
+    pub fn is_fresh(&self, index: usize) -> bool {
+        self.consumed.get(index).map(|c| !*c).unwrap_or(false)
+    }
+
+    /// Mark the randomness at index `index` of the batch as used. Returns an error if it was already used.
+    pub fn consume(&mut self, index: usize) -> Result<(), BBSPlusError> {
+        match self.consumed.get_mut(index) {
+            Some(true) => Err(BBSPlusError::NonceReused(index)),
+            Some(c) => {
+                *c = true;
+                Ok(())
+            }
+            None => Err(BBSPlusError::SignatureIndexOutOfBatch(
+                index,
+                self.batch_size,
+            )),
+        }
+    }
+}
+
+impl_signature_share!(
+    BBSPlusSignatureShare,
+    Aggregator,
+    G1Affine,
+    G1,
+    SignatureParamsG1,
+    SignatureG1
+);
+impl_signature_share!(
+    BBSPlusSignatureShareG2,
+    AggregatorG2,
+    G2Affine,
+    G2,
+    SignatureParamsG2,
+    SignatureG2
+);
+
 impl<E: Pairing> BBSPlusSignatureShare<E> {
-    /// `sig_index_in_batch` is the index of this signature in batch and also in the Phase1 and Phase2 outputs
-    pub fn new(
-        messages: &[E::ScalarField],
-        sig_index_in_batch: usize,
-        phase1: &Phase1Output<E::ScalarField>,
-        phase2: &Phase2Output<E::ScalarField>,
-        sig_params: &SignatureParamsG1<E>,
-    ) -> Result<Self, BBSPlusError> {
-        if messages.is_empty() {
-            return Err(BBSPlusError::NoMessageToSign);
+    /// Same as `Self::aggregate` but also returns a transcript of the aggregation which records the ordered
+    /// participant ids and the hash of each share's `(u, R)`
+    pub fn aggregate_with_transcript<D: Digest>(
+        sig_shares: Vec<Self>,
+    ) -> Result<(SignatureG1<E>, AggregationTranscript), BBSPlusError> {
+        let mut participants = Vec::with_capacity(sig_shares.len());
+        let mut share_hashes = Vec::with_capacity(sig_shares.len());
+        for share in &sig_shares {
+            participants.push(share.id);
+            share_hashes.push(share.hash_u_and_R::<D>()?);
         }
-        expect_equality!(
-            messages.len(),
-            sig_params.supported_message_count(),
-            BBSPlusError::MessageCountIncompatibleWithSigParams
-        );
-        // Create map of msg index (0-based) -> message
-        let msg_map: BTreeMap<usize, &E::ScalarField> =
-            messages.iter().enumerate().map(|(i, e)| (i, e)).collect();
-        Self::new_with_committed_messages(
-            &E::G1Affine::zero(),
-            msg_map,
-            sig_index_in_batch,
-            phase1,
-            phase2,
-            sig_params,
-        )
+        let sig = Self::aggregate(sig_shares)?;
+        Ok((
+            sig,
+            AggregationTranscript {
+                participants,
+                share_hashes,
+            },
+        ))
     }
 
-    /// `sig_index_in_batch` is the index of this signature in batch and also in the Phase1 and Phase2 outputs
-    pub fn new_with_committed_messages(
-        commitment: &E::G1Affine,
-        uncommitted_messages: BTreeMap<usize, &E::ScalarField>,
-        sig_index_in_batch: usize,
-        phase1: &Phase1Output<E::ScalarField>,
-        phase2: &Phase2Output<E::ScalarField>,
-        sig_params: &SignatureParamsG1<E>,
-    ) -> Result<Self, BBSPlusError> {
-        let b = sig_params.b(uncommitted_messages, &phase1.s[sig_index_in_batch])?;
-        let commitment_plus_b = b + commitment;
-        let (R, u) = compute_R_and_u(
-            commitment_plus_b,
-            &phase1.r[sig_index_in_batch],
-            &phase1.e[sig_index_in_batch],
-            &phase1.masked_rs[sig_index_in_batch],
-            &phase1.masked_signing_key_shares[sig_index_in_batch],
-            sig_index_in_batch as u32,
-            phase2,
-        );
-        Ok(Self {
-            id: phase1.id,
-            e: phase1.e[sig_index_in_batch],
-            s: phase1.s[sig_index_in_batch],
-            u,
-            R,
-        })
+    fn hash_u_and_R<D: Digest>(&self) -> Result<Vec<u8>, BBSPlusError> {
+        let mut bytes = Vec::new();
+        self.u.serialize_compressed(&mut bytes)?;
+        self.R.serialize_compressed(&mut bytes)?;
+        Ok(D::digest(&bytes).to_vec())
     }
+}
 
-    pub fn aggregate(sig_shares: Vec<Self>) -> Result<SignatureG1<E>, BBSPlusError> {
-        // TODO: Ensure correct threshold. Share should contain threshold and share id
-        let mut sum_R = E::G1::zero();
-        let mut sum_u = E::ScalarField::zero();
-        let mut expected_e = E::ScalarField::zero();
-        let mut expected_s = E::ScalarField::zero();
-        for (i, share) in sig_shares.into_iter().enumerate() {
-            if i == 0 {
-                expected_e = share.e;
-                expected_s = share.s;
-            } else {
-                if expected_e != share.e {
-                    return Err(BBSPlusError::IncorrectEByParticipant(share.id));
-                }
-                if expected_s != share.s {
-                    return Err(BBSPlusError::IncorrectSByParticipant(share.id));
-                }
+impl AggregationTranscript {
+    /// Check that `sig_shares`, in the given order, are the ones recorded in this transcript and that
+    /// aggregating them gives the signature `sig`
+    pub fn verify<E: Pairing, D: Digest>(
+        &self,
+        sig_shares: Vec<BBSPlusSignatureShare<E>>,
+        sig: &SignatureG1<E>,
+    ) -> Result<(), BBSPlusError> {
+        if self.participants.len() != sig_shares.len()
+            || self.share_hashes.len() != sig_shares.len()
+        {
+            return Err(BBSPlusError::InvalidAggregationTranscript);
+        }
+        for ((id, hash), share) in self
+            .participants
+            .iter()
+            .zip(self.share_hashes.iter())
+            .zip(sig_shares.iter())
+        {
+            if *id != share.id || *hash != share.hash_u_and_R::<D>()? {
+                return Err(BBSPlusError::InvalidAggregationTranscript);
             }
-            sum_u += share.u;
-            sum_R += share.R;
         }
-        let A = sum_R * sum_u.inverse().unwrap();
-        Ok(SignatureG1 {
-            A: A.into_affine(),
-            e: expected_e,
-            s: expected_s,
-        })
+        if BBSPlusSignatureShare::aggregate(sig_shares)? != *sig {
+            return Err(BBSPlusError::InvalidAggregationTranscript);
+        }
+        Ok(())
     }
 }
 
@@ -208,10 +765,12 @@ pub mod tests {
     use std::time::{Duration, Instant};
 
     use crate::{
-        setup::{PublicKeyG2, SecretKey},
+        setup::{PublicKeyG1, PublicKeyG2, SecretKey},
         threshold::multiplication_phase::Phase2,
     };
     use oblivious_transfer_protocols::ot_based_multiplication::{
+        base_ot_multi_party_pairwise::BaseOTOutput,
+        batch_mul_multi_party::ParticipantOutput as MultiplicationPartyOutput,
         dkls18_mul_2p::MultiplicationOTEParams, dkls19_batch_mul_2p::GadgetVector,
     };
 
@@ -224,6 +783,12 @@ pub mod tests {
     use secret_sharing_and_dkg::shamir_ss::deal_random_secret;
     use test_utils::ot::do_pairwise_base_ot;
 
+    const BASE_OT_KEY_SIZE: u16 = 128;
+    const KAPPA: u16 = 256;
+    const STATISTICAL_SECURITY_PARAMETER: u16 = 80;
+    const OTE_PARAMS: MultiplicationOTEParams<KAPPA, STATISTICAL_SECURITY_PARAMETER> =
+        MultiplicationOTEParams::<KAPPA, STATISTICAL_SECURITY_PARAMETER> {};
+
     pub fn trusted_party_keygen<R: RngCore, F: PrimeField>(
         rng: &mut R,
         threshold: ParticipantId,
@@ -233,12 +798,231 @@ pub mod tests {
         (secret, shares.0.into_iter().map(|s| s.share).collect())
     }
 
+    /// Run phase 1 for the given threshold number of signers, each using its share of the signing key
+    pub fn do_phase1(
+        rng: &mut StdRng,
+        threshold_signers: ParticipantId,
+        sig_batch_size: u32,
+        sk_shares: &[Fr],
+        protocol_id: Vec<u8>,
+    ) -> Vec<Phase1Output<Fr>> {
+        // Signers finish round-1 to generate the output
+        do_phase1_rounds(rng, threshold_signers, sig_batch_size, protocol_id)
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| p.finish_for_bbs_plus::<Blake2b512>(&sk_shares[i]).unwrap())
+            .collect::<Vec<_>>()
+    }
+
+    /// Run the rounds of phase 1 for the given threshold number of signers but don't finish it
+    pub fn do_phase1_rounds(
+        rng: &mut StdRng,
+        threshold_signers: ParticipantId,
+        sig_batch_size: u32,
+        protocol_id: Vec<u8>,
+    ) -> Vec<Phase1<Fr, 256>> {
+        let threshold_party_set = (1..=threshold_signers).collect::<BTreeSet<_>>();
+
+        let mut phase1s = vec![];
+        let mut commitments = vec![];
+        let mut commitments_zero_share = vec![];
+
+        // Signers initiate round-1 and each signer sends commitments to others
+        for i in 1..=threshold_signers {
+            let mut others = threshold_party_set.clone();
+            others.remove(&i);
+            let (round1, comm, comm_zero) = Phase1::<Fr, 256>::init_for_bbs_plus(
+                rng,
+                sig_batch_size,
+                i,
+                others,
+                protocol_id.clone(),
+            )
+            .unwrap();
+            phase1s.push(round1);
+            commitments.push(comm);
+            commitments_zero_share.push(comm_zero);
+        }
+
+        // Signers process round-1 commitments received from others
+        for i in 1..=threshold_signers {
+            for j in 1..=threshold_signers {
+                if i != j {
+                    phase1s[i as usize - 1]
+                        .receive_commitment(
+                            j,
+                            commitments[j as usize - 1].clone(),
+                            commitments_zero_share[j as usize - 1]
+                                .get(&i)
+                                .unwrap()
+                                .clone(),
+                        )
+                        .unwrap();
+                }
+            }
+        }
+
+        // Signers create round-1 shares for all others once they have the required commitments from others
+        let mut received = (1..=threshold_signers)
+            .map(|_| BTreeMap::new())
+            .collect::<Vec<_>>();
+        for j in 1..=threshold_signers {
+            let mut others = threshold_party_set.clone();
+            others.remove(&j);
+            for (i, shares) in phase1s[j as usize - 1].get_all_shares_for(&others).unwrap() {
+                received[i as usize - 1].insert(j, shares);
+            }
+        }
+        for (phase1, shares) in phase1s.iter_mut().zip(received) {
+            phase1.receive_shares_bulk(shares).unwrap();
+        }
+        phase1s
+    }
+
+    /// Run phase 2 for the given threshold number of signers using their phase 1 outputs
+    pub fn do_phase2(
+        rng: &mut StdRng,
+        threshold_signers: ParticipantId,
+        gadget_vector: &GadgetVector<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>,
+        base_ot_outputs: &[BaseOTOutput],
+        phase1_outs: &[Phase1Output<Fr>],
+    ) -> Vec<Phase2Output<Fr>> {
+        let threshold_party_set = (1..=threshold_signers).collect::<BTreeSet<_>>();
+
+        let mut phase2s = vec![];
+        let mut all_msg_1s = vec![];
+
+        // Signers initiate round-2 and each signer sends messages to others
+        for i in 1..=threshold_signers {
+            let mut others = threshold_party_set.clone();
+            others.remove(&i);
+            let (phase, U) = Phase2::init(
+                rng,
+                i,
+                phase1_outs[i as usize - 1]
+                    .masked_signing_key_shares
+                    .clone(),
+                phase1_outs[i as usize - 1].masked_rs.clone(),
+                base_ot_outputs[i as usize - 1].clone(),
+                others,
+                OTE_PARAMS,
+                gadget_vector,
+            )
+            .unwrap();
+            phase2s.push(phase);
+            all_msg_1s.push((i, U));
+        }
+
+        // Signers process round-2 messages received from others
+        let mut all_msg_2s = vec![];
+        for (sender_id, msg_1s) in all_msg_1s {
+            for (receiver_id, m) in msg_1s {
+                let m2 = phase2s[receiver_id as usize - 1]
+                    .receive_message1::<Blake2b512>(sender_id, m, gadget_vector)
+                    .unwrap();
+                all_msg_2s.push((receiver_id, sender_id, m2));
+            }
+        }
+
+        for (sender_id, receiver_id, m2) in all_msg_2s {
+            phase2s[receiver_id as usize - 1]
+                .receive_message2::<Blake2b512>(sender_id, m2, gadget_vector)
+                .unwrap();
+        }
+
+        phase2s.into_iter().map(|p| p.finish()).collect::<Vec<_>>()
+    }
+
+    pub type SetupAndPhaseOutputs = (
+        SignatureParamsG1<Bls12_381>,
+        PublicKeyG2<Bls12_381>,
+        Vec<Phase1Output<Fr>>,
+        Vec<Phase2Output<Fr>>,
+    );
+
+    /// Do the one time setup (keygen and base OT) and run phase 1 and 2 for the given threshold number
+    /// of signers. Returns the signature params, public key and outputs of phase 1 and 2 of each signer.
+    pub fn setup_and_do_phase1_and_phase2(
+        rng: &mut StdRng,
+        threshold_signers: ParticipantId,
+        total_signers: ParticipantId,
+        sig_batch_size: u32,
+        message_count: u32,
+    ) -> SetupAndPhaseOutputs {
+        let (sk, sk_shares) = trusted_party_keygen::<_, Fr>(rng, threshold_signers, total_signers);
+        let params = SignatureParamsG1::<Bls12_381>::generate_using_rng(rng, message_count);
+        let public_key = PublicKeyG2::generate_using_secret_key(&SecretKey(sk), &params);
+        let (phase1_outs, phase2_outs) = do_base_ot_and_phase1_and_phase2(
+            rng,
+            threshold_signers,
+            total_signers,
+            sig_batch_size,
+            &sk_shares,
+        );
+        (params, public_key, phase1_outs, phase2_outs)
+    }
+
+    /// Run the base OT among all signers and then phase 1 and 2 for the given threshold number of signers
+    pub fn do_base_ot_and_phase1_and_phase2(
+        rng: &mut StdRng,
+        threshold_signers: ParticipantId,
+        total_signers: ParticipantId,
+        sig_batch_size: u32,
+        sk_shares: &[Fr],
+    ) -> (Vec<Phase1Output<Fr>>, Vec<Phase2Output<Fr>>) {
+        let gadget_vector = GadgetVector::<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>::new::<
+            Blake2b512,
+        >(OTE_PARAMS, b"test-gadget-vector");
+        let total_party_set = (1..=total_signers).collect::<BTreeSet<_>>();
+        let base_ot_outputs = do_pairwise_base_ot::<BASE_OT_KEY_SIZE>(
+            rng,
+            OTE_PARAMS.num_base_ot(),
+            total_signers,
+            total_party_set,
+        );
+        let phase1_outs = do_phase1(
+            rng,
+            threshold_signers,
+            sig_batch_size,
+            sk_shares,
+            b"test".to_vec(),
+        );
+        let phase2_outs = do_phase2(
+            rng,
+            threshold_signers,
+            &gadget_vector,
+            &base_ot_outputs,
+            &phase1_outs,
+        );
+        (phase1_outs, phase2_outs)
+    }
+
+    /// Create the signature shares of the first `signers` signers for the signature at index `sig_index_in_batch`
+    pub fn create_shares(
+        messages: &[Fr],
+        sig_index_in_batch: usize,
+        signers: ParticipantId,
+        phase1_outs: &mut [Phase1Output<Fr>],
+        phase2_outs: &[Phase2Output<Fr>],
+        params: &SignatureParamsG1<Bls12_381>,
+    ) -> Vec<BBSPlusSignatureShare<Bls12_381>> {
+        (0..signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new(
+                    messages,
+                    sig_index_in_batch,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    params,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
     #[test]
     fn signing() {
         let mut rng = StdRng::seed_from_u64(0u64);
-        const BASE_OT_KEY_SIZE: u16 = 128;
-        const KAPPA: u16 = 256;
-        const STATISTICAL_SECURITY_PARAMETER: u16 = 80;
         let ote_params = MultiplicationOTEParams::<KAPPA, STATISTICAL_SECURITY_PARAMETER> {};
         let gadget_vector = GadgetVector::<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>::new::<
             Blake2b512,
@@ -255,8 +1039,8 @@ pub mod tests {
         ) {
             let protocol_id = b"test".to_vec();
 
-            let total_party_set = (1..=total_signers).into_iter().collect::<BTreeSet<_>>();
-            let threshold_party_set = (1..=threshold_signers).into_iter().collect::<BTreeSet<_>>();
+            let total_party_set = (1..=total_signers).collect::<BTreeSet<_>>();
+            let threshold_party_set = (1..=threshold_signers).collect::<BTreeSet<_>>();
             // The signers do a keygen. This is a one time setup.
             let (sk, sk_shares) =
                 trusted_party_keygen::<_, Fr>(rng, threshold_signers, total_signers);
@@ -284,7 +1068,6 @@ pub mod tests {
             let mut commitments = vec![];
             let mut commitments_zero_share = vec![];
             let mut round1outs = vec![];
-            let total_phase1_time;
             let mut phase1_times = BTreeMap::new();
 
             // Signers initiate round-1 and each signer sends commitments to others
@@ -357,7 +1140,7 @@ pub mod tests {
                 expected_sk += out.masked_signing_key_shares.iter().sum::<Fr>();
                 round1outs.push(out);
             }
-            total_phase1_time = start.elapsed();
+            let total_phase1_time = start.elapsed();
             println!("Phase 1 took {:?}", total_phase1_time);
 
             assert_eq!(expected_sk, sk * Fr::from(sig_batch_size));
@@ -368,7 +1151,6 @@ pub mod tests {
 
             let mut round2s = vec![];
             let mut all_msg_1s = vec![];
-            let total_phase2_time;
             let mut phase2_times = BTreeMap::new();
 
             // Signers initiate round-2 and each signer sends messages to others
@@ -385,7 +1167,7 @@ pub mod tests {
                     base_ot_outputs[i as usize - 1].clone(),
                     others,
                     ote_params,
-                    &gadget_vector,
+                    gadget_vector,
                 )
                 .unwrap();
                 phase2_times.insert(i, start.elapsed());
@@ -399,7 +1181,7 @@ pub mod tests {
                 for (receiver_id, m) in msg_1s {
                     let start = Instant::now();
                     let m2 = round2s[receiver_id as usize - 1]
-                        .receive_message1::<Blake2b512>(sender_id, m, &gadget_vector)
+                        .receive_message1::<Blake2b512>(sender_id, m, gadget_vector)
                         .unwrap();
                     phase2_times.insert(
                         receiver_id,
@@ -412,7 +1194,7 @@ pub mod tests {
             for (sender_id, receiver_id, m2) in all_msg_2s {
                 let start = Instant::now();
                 round2s[receiver_id as usize - 1]
-                    .receive_message2::<Blake2b512>(sender_id, m2, &gadget_vector)
+                    .receive_message2::<Blake2b512>(sender_id, m2, gadget_vector)
                     .unwrap();
                 phase2_times.insert(
                     receiver_id,
@@ -430,7 +1212,7 @@ pub mod tests {
                     o
                 })
                 .collect::<Vec<_>>();
-            total_phase2_time = start.elapsed();
+            let total_phase2_time = start.elapsed();
             println!("Phase 2 took {:?}", total_phase2_time);
 
             // Check that multiplication phase ran successfully, i.e. each signer has an additive share of
@@ -473,7 +1255,7 @@ pub mod tests {
                     let share = BBSPlusSignatureShare::new(
                         &messages,
                         k,
-                        &round1outs[i],
+                        &mut round1outs[i],
                         &round2_outputs[i],
                         &params,
                     )
@@ -527,4 +1309,857 @@ pub mod tests {
         check(&mut rng, ote_params, 10, 20, 10, 3, &gadget_vector);
         check(&mut rng, ote_params, 20, 30, 10, 3, &gadget_vector);
     }
+
+    #[test]
+    fn malformed_share_fails_signature_verification() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 4;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 1, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shares = create_shares(
+            &messages,
+            0,
+            threshold_signers,
+            &mut phase1_outs,
+            &phase2_outs,
+            &params,
+        );
+        let sig = BBSPlusSignatureShare::aggregate(shares.clone()).unwrap();
+        sig.verify(&messages, public_key.clone(), params.clone())
+            .unwrap();
+
+        let commitment_plus_b = params
+            .b(messages.iter().enumerate(), &shares[0].s)
+            .unwrap()
+            .into_affine();
+
+        // A signer picks its own randomness and sends a consistent `R` for it but its `u` is computed
+        // with the randomness from phase 1
+        let mut bad_shares = shares.clone();
+        bad_shares[0].R = (commitment_plus_b * Fr::rand(&mut rng)).into_affine();
+        let sig = BBSPlusSignatureShare::aggregate(bad_shares).unwrap();
+        assert!(sig
+            .verify(&messages, public_key.clone(), params.clone())
+            .is_err());
+
+        // A signer sends a correct `R` but an incorrect `u`
+        let mut bad_shares = shares;
+        bad_shares[1].u += Fr::rand(&mut rng);
+        let sig = BBSPlusSignatureShare::aggregate(bad_shares).unwrap();
+        assert!(sig.verify(&messages, public_key, params).is_err());
+    }
+
+    #[test]
+    fn uncommitted_message_index_out_of_bounds() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 4;
+        let (params, _, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, 2, 3, 1, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let commitment = params
+            .commit_to_messages(BTreeMap::from([(0, &messages[0])]), &Fr::rand(&mut rng))
+            .unwrap();
+        let mut uncommitted = BTreeMap::from([(1, &messages[1]), (2, &messages[2])]);
+        uncommitted.insert(message_count as usize, &messages[3]);
+        assert!(matches!(
+            BBSPlusSignatureShare::new_with_committed_messages(
+                &commitment,
+                uncommitted,
+                0,
+                &mut phase1_outs[0],
+                &phase2_outs[0],
+                &params,
+            ),
+            Err(BBSPlusError::MessageIndexOutOfBounds { index, max }) if index == message_count as usize && max == message_count as usize
+        ));
+
+        let uncommitted = BTreeMap::from([(1, &messages[1]), (3, &messages[3])]);
+        assert!(BBSPlusSignatureShare::new_with_committed_messages(
+            &commitment,
+            uncommitted,
+            0,
+            &mut phase1_outs[0],
+            &phase2_outs[0],
+            &params,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn sparse_uncommitted_messages() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 100;
+        let uncommitted_index = 73;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 1, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let blinding = Fr::rand(&mut rng);
+        let commitment = params
+            .commit_to_messages(
+                messages
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != uncommitted_index),
+                &blinding,
+            )
+            .unwrap();
+
+        // `b` for a single high index only uses `h` at that index
+        let s = Fr::rand(&mut rng);
+        let uncommitted = BTreeMap::from([(uncommitted_index, &messages[uncommitted_index])]);
+        assert_eq!(
+            params.b(uncommitted.clone(), &s).unwrap(),
+            params.g1 + params.h_0 * s + params.h[uncommitted_index] * messages[uncommitted_index]
+        );
+
+        let shares = (0..threshold_signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new_with_committed_messages(
+                    &commitment,
+                    uncommitted.clone(),
+                    0,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let sig = BBSPlusSignatureShare::aggregate(shares)
+            .unwrap()
+            .unblind(&blinding);
+        sig.verify(&messages, public_key, params).unwrap();
+    }
+
+    #[test]
+    fn uncompressed_serialization() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 5;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 1, message_count);
+
+        for phase1_out in &phase1_outs {
+            let bytes = phase1_out.serialize_uncompressed_to_vec().unwrap();
+            let mut compressed = vec![];
+            phase1_out.serialize_compressed(&mut compressed).unwrap();
+            assert_eq!(bytes, compressed);
+            assert_eq!(
+                Phase1Output::<Fr>::deserialize_uncompressed_from(&bytes[..]).unwrap(),
+                *phase1_out
+            );
+        }
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shares = create_shares(
+            &messages,
+            0,
+            threshold_signers,
+            &mut phase1_outs,
+            &phase2_outs,
+            &params,
+        );
+
+        let mut deserialized = vec![];
+        for share in &shares {
+            let bytes = share.serialize_uncompressed_to_vec().unwrap();
+            let mut compressed = vec![];
+            share.serialize_compressed(&mut compressed).unwrap();
+            assert!(bytes.len() > compressed.len());
+            let from_uncompressed =
+                BBSPlusSignatureShare::<Bls12_381>::deserialize_uncompressed_from(&bytes[..])
+                    .unwrap();
+            let from_compressed =
+                BBSPlusSignatureShare::<Bls12_381>::deserialize_compressed(&compressed[..])
+                    .unwrap();
+            assert_eq!(from_uncompressed, *share);
+            assert_eq!(from_compressed, *share);
+            deserialized.push(from_uncompressed);
+        }
+        // Truncated bytes are rejected
+        let bytes = shares[0].serialize_uncompressed_to_vec().unwrap();
+        assert!(
+            BBSPlusSignatureShare::<Bls12_381>::deserialize_uncompressed_from(
+                &bytes[..bytes.len() - 1]
+            )
+            .is_err()
+        );
+
+        let sig = BBSPlusSignatureShare::aggregate(deserialized).unwrap();
+        sig.verify(&messages, public_key, params).unwrap();
+    }
+
+    #[test]
+    fn aggregation_with_transcript() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 4;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 2, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shares = create_shares(
+            &messages,
+            0,
+            threshold_signers,
+            &mut phase1_outs,
+            &phase2_outs,
+            &params,
+        );
+        let (sig, transcript) =
+            BBSPlusSignatureShare::aggregate_with_transcript::<Blake2b512>(shares.clone()).unwrap();
+        sig.verify(&messages, public_key.clone(), params.clone())
+            .unwrap();
+        assert_eq!(
+            sig,
+            BBSPlusSignatureShare::aggregate(shares.clone()).unwrap()
+        );
+        assert_eq!(transcript.participants, vec![1, 2, 3]);
+        transcript
+            .verify::<_, Blake2b512>(shares.clone(), &sig)
+            .unwrap();
+
+        // Missing, reordered or replaced shares don't match the transcript
+        assert!(transcript
+            .verify::<_, Blake2b512>(shares[..2].to_vec(), &sig)
+            .is_err());
+        let mut reordered = shares.clone();
+        reordered.swap(0, 1);
+        assert!(transcript.verify::<_, Blake2b512>(reordered, &sig).is_err());
+        let mut replaced = shares.clone();
+        replaced[2] =
+            BBSPlusSignatureShare::new(&messages, 1, &mut phase1_outs[2], &phase2_outs[2], &params)
+                .unwrap();
+        assert!(transcript.verify::<_, Blake2b512>(replaced, &sig).is_err());
+
+        // Shares match but the signature isn't their aggregation
+        let mut other_sig = sig.clone();
+        other_sig.A = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        assert!(transcript
+            .verify::<_, Blake2b512>(shares, &other_sig)
+            .is_err());
+    }
+
+    #[test]
+    fn phase1_randomness_reuse() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let message_count = 4;
+        let batch_size = 2;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, 2, 3, batch_size, message_count);
+
+        let messages_1 = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let messages_2 = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+
+        assert!(phase1_outs[0].is_fresh(0));
+        assert!(phase1_outs[0].is_fresh(1));
+        assert!(!phase1_outs[0].is_fresh(batch_size as usize));
+
+        let shares = create_shares(&messages_1, 0, 2, &mut phase1_outs, &phase2_outs, &params);
+        assert!(!phase1_outs[0].is_fresh(0));
+        assert!(phase1_outs[0].is_fresh(1));
+        let sig = BBSPlusSignatureShare::aggregate(shares).unwrap();
+        sig.verify(&messages_1, public_key.clone(), params.clone())
+            .unwrap();
+
+        // Signing different messages with the same randomness fails
+        assert!(matches!(
+            BBSPlusSignatureShare::new(
+                &messages_2,
+                0,
+                &mut phase1_outs[0],
+                &phase2_outs[0],
+                &params,
+            ),
+            Err(BBSPlusError::NonceReused(0))
+        ));
+        assert!(matches!(
+            phase1_outs[1].consume(0),
+            Err(BBSPlusError::NonceReused(0))
+        ));
+        assert!(matches!(
+            BBSPlusSignatureShare::new(
+                &messages_2,
+                batch_size as usize,
+                &mut phase1_outs[0],
+                &phase2_outs[0],
+                &params,
+            ),
+            Err(BBSPlusError::SignatureIndexOutOfBatch(2, 2))
+        ));
+
+        // Next index of the batch can still be used
+        let shares = create_shares(&messages_2, 1, 2, &mut phase1_outs, &phase2_outs, &params);
+        let sig = BBSPlusSignatureShare::aggregate(shares).unwrap();
+        sig.verify(&messages_2, public_key, params).unwrap();
+    }
+
+    #[test]
+    fn shares_with_incompatible_ote_params() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 4;
+        let (params, _, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 1, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let mut shares = (0..threshold_signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new(
+                    &messages,
+                    0,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        for share in &shares {
+            assert_eq!(share.ote_params.kappa, KAPPA);
+            assert_eq!(
+                share.ote_params.statistical_security_parameter,
+                STATISTICAL_SECURITY_PARAMETER
+            );
+        }
+
+        shares[2].ote_params.statistical_security_parameter = 128;
+        assert!(matches!(
+            BBSPlusSignatureShare::aggregate(shares),
+            Err(BBSPlusError::IncompatibleOTEParams(3))
+        ));
+    }
+
+    #[test]
+    fn incremental_aggregation() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 4;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 2, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let mut shares = vec![];
+        let mut aggregator = Aggregator::new();
+        assert!(matches!(
+            aggregator.clone().finalize(),
+            Err(BBSPlusError::NoSignatureShares)
+        ));
+        assert!(matches!(
+            aggregator.clone().into_parts(),
+            Err(BBSPlusError::NoSignatureShares)
+        ));
+        assert!(matches!(
+            BBSPlusSignatureShare::<Bls12_381>::aggregate(vec![]),
+            Err(BBSPlusError::NoSignatureShares)
+        ));
+        for i in 0..threshold_signers as usize {
+            let share = BBSPlusSignatureShare::new(
+                &messages,
+                0,
+                &mut phase1_outs[i],
+                &phase2_outs[i],
+                &params,
+            )
+            .unwrap();
+            aggregator.add_share(share.clone()).unwrap();
+            shares.push(share);
+        }
+        let sig = aggregator.finalize().unwrap();
+        sig.verify(&messages, public_key, params.clone()).unwrap();
+        assert_eq!(
+            sig,
+            BBSPlusSignatureShare::aggregate(shares.clone()).unwrap()
+        );
+        assert_eq!(
+            sig,
+            BBSPlusSignatureShare::aggregate_from_iter(shares.iter().cloned()).unwrap()
+        );
+
+        let (sig_with_participants, participants) =
+            BBSPlusSignatureShare::aggregate_with_participants(shares.clone()).unwrap();
+        assert_eq!(sig_with_participants, sig);
+        assert_eq!(
+            participants,
+            (1..=threshold_signers).collect::<BTreeSet<_>>()
+        );
+        let mut repeated = shares.clone();
+        repeated.push(shares[1].clone());
+        assert!(matches!(
+            BBSPlusSignatureShare::aggregate_with_participants(repeated),
+            Err(BBSPlusError::AlreadyHaveSharesFromParticipant(2))
+        ));
+
+        // A share for another signature of the batch has different `e` and `s`
+        let other =
+            BBSPlusSignatureShare::new(&messages, 1, &mut phase1_outs[1], &phase2_outs[1], &params)
+                .unwrap();
+        let mut aggregator = Aggregator::new();
+        aggregator.add_share(shares[0].clone()).unwrap();
+        assert!(matches!(
+            aggregator.add_share(other.clone()),
+            Err(BBSPlusError::IncorrectEByParticipant(2))
+        ));
+        assert!(matches!(
+            BBSPlusSignatureShare::aggregate_from_iter(
+                iter::once(shares[0].clone()).chain(iter::once(other))
+            ),
+            Err(BBSPlusError::IncorrectEByParticipant(2))
+        ));
+    }
+
+    #[test]
+    fn malformed_phase1_output_deserialization() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, sk_shares) = trusted_party_keygen::<_, Fr>(&mut rng, 3, 5);
+        let phase1_out = do_phase1(&mut rng, 3, 2, &sk_shares, b"test".to_vec()).remove(0);
+        let mut bytes = vec![];
+        phase1_out.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            Phase1Output::<Fr>::deserialize_compressed(&bytes[..]).unwrap(),
+            phase1_out
+        );
+
+        // Truncated and corrupted bytes give an error, never a panic
+        for i in 0..bytes.len() {
+            assert!(Phase1Output::<Fr>::deserialize_compressed(&bytes[..i]).is_err());
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= 0xff;
+            let _ = Phase1Output::<Fr>::deserialize_compressed(&corrupted[..]);
+            let _ = Phase1Output::<Fr>::deserialize_compressed_unchecked(&corrupted[..]);
+        }
+
+        // Vectors not matching the batch size are rejected even when not validating
+        let mut bad = phase1_out.clone();
+        bad.consumed.pop();
+        let mut bytes = vec![];
+        bad.serialize_compressed(&mut bytes).unwrap();
+        assert!(Phase1Output::<Fr>::deserialize_compressed(&bytes[..]).is_err());
+        assert!(Phase1Output::<Fr>::deserialize_compressed_unchecked(&bytes[..]).is_err());
+        let mut bad = phase1_out;
+        bad.batch_size = 3;
+        let mut bytes = vec![];
+        bad.serialize_compressed(&mut bytes).unwrap();
+        assert!(Phase1Output::<Fr>::deserialize_compressed_unchecked(&bytes[..]).is_err());
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_phase1_output() {
+        use arbitrary::{Arbitrary, Unstructured};
+        use ark_std::rand::RngCore;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for _ in 0..10 {
+            let mut data = vec![0u8; 2048];
+            rng.fill_bytes(&mut data);
+            let phase1_out = Phase1Output::<Fr>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let mut bytes = vec![];
+            phase1_out.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(
+                Phase1Output::<Fr>::deserialize_compressed(&bytes[..]).unwrap(),
+                phase1_out
+            );
+        }
+    }
+
+    #[test]
+    fn salt_size_check() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let others = BTreeSet::from([2, 3]);
+        assert!(matches!(
+            Phase1::<Fr, 4>::init_for_bbs_plus(&mut rng, 1, 1, others.clone(), b"test".to_vec()),
+            Err(BBSPlusError::SaltTooSmall(4))
+        ));
+        assert!(matches!(
+            Phase1::<Fr, 15>::init_for_bbs(&mut rng, 1, 1, others.clone(), b"test".to_vec()),
+            Err(BBSPlusError::SaltTooSmall(15))
+        ));
+        assert!(Phase1::<Fr, MIN_SALT_SIZE>::init_for_bbs_plus(
+            &mut rng,
+            1,
+            1,
+            others,
+            b"test".to_vec()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn phase1_with_dyn_rng() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let dyn_rng: &mut dyn RngCore = &mut rng;
+        let (phase1, comm, comm_zero) = Phase1::<Fr, 256>::init_for_bbs_plus(
+            dyn_rng,
+            2,
+            1,
+            BTreeSet::from([2, 3]),
+            b"test".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(phase1.r.len(), 2);
+        assert_eq!(comm.0.len(), 4);
+        assert_eq!(comm_zero.len(), 2);
+        assert!(Phase1::<Fr, 256>::init_for_bbs(
+            dyn_rng,
+            2,
+            1,
+            BTreeSet::from([2, 3]),
+            b"test".to_vec()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn verify_phase2_outputs() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (_, _, phase1_outs, phase2_outs) = setup_and_do_phase1_and_phase2(&mut rng, 3, 5, 2, 5);
+        let verify = |out_1: &Phase2Output<Fr>, out_2: &Phase2Output<Fr>| {
+            out_1.verify_against(
+                out_2,
+                (1, 2),
+                &phase1_outs[0].masked_signing_key_shares,
+                &phase1_outs[0].masked_rs,
+                &phase1_outs[1].masked_signing_key_shares,
+                &phase1_outs[1].masked_rs,
+            )
+        };
+        verify(&phase2_outs[0], &phase2_outs[1]).unwrap();
+
+        let mut corrupted = phase2_outs[1].clone();
+        corrupted.0.z_A.get_mut(&1).unwrap().1[1] += Fr::from(1u64);
+        assert!(matches!(
+            verify(&phase2_outs[0], &corrupted),
+            Err(BBSPlusError::InconsistentProduct { with: 2, index: 1 })
+        ));
+
+        let mut missing = phase2_outs[1].clone();
+        missing.0.z_A.remove(&1);
+        assert!(matches!(
+            verify(&phase2_outs[0], &missing),
+            Err(BBSPlusError::UnexpectedParticipant(2))
+        ));
+    }
+
+    #[test]
+    fn merge_phase2_outputs() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 4;
+        let message_count = 5;
+        let (params, public_key, mut phase1_outs, mut phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 1, message_count);
+
+        // Split the output of the first signer as if the multiplication with signer 4 was done separately
+        let output = phase2_outs[0].clone();
+        let mut first = output.clone();
+        let late_z_A = first.0.z_A.split_off(&4);
+        let late_z_B = first.0.z_B.split_off(&4);
+        let late = Phase2Output(
+            MultiplicationPartyOutput {
+                z_A: late_z_A,
+                z_B: late_z_B,
+            },
+            output.1,
+        );
+        assert_eq!(first.clone().merge(late.clone()).unwrap(), output);
+        // Repeated equal entries are fine
+        assert_eq!(output.clone().merge(late.clone()).unwrap(), output);
+
+        let mut conflicting = late.clone();
+        conflicting
+            .0
+            .z_A
+            .values_mut()
+            .chain(conflicting.0.z_B.values_mut())
+            .next()
+            .unwrap()
+            .0[0] += Fr::from(1u64);
+        assert!(matches!(
+            output.clone().merge(conflicting),
+            Err(BBSPlusError::ConflictingMultiplicationOutput(4))
+        ));
+        let mut other_params = late.clone();
+        other_params.1.kappa += 1;
+        assert!(matches!(
+            first.clone().merge(other_params),
+            Err(BBSPlusError::MismatchedOTEParams)
+        ));
+
+        phase2_outs[0] = first.merge(late).unwrap();
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shares = create_shares(
+            &messages,
+            0,
+            threshold_signers,
+            &mut phase1_outs,
+            &phase2_outs,
+            &params,
+        );
+        let sig = BBSPlusSignatureShare::aggregate(shares).unwrap();
+        sig.verify(&messages, public_key, params).unwrap();
+    }
+
+    #[test]
+    fn best_effort_aggregation() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let message_count = 5;
+        let (params, public_key, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 5, 1, message_count);
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let mut shares = (0..threshold_signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new(
+                    &messages,
+                    0,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // A share from another session and a repeated share are discarded
+        let mut other_session = shares[0].clone();
+        other_session.id = 4;
+        other_session.e += Fr::from(1u64);
+        shares.insert(1, other_session);
+        shares.push(shares[2].clone());
+        assert!(BBSPlusSignatureShare::aggregate(shares.clone()).is_err());
+
+        let signers = phase1_outs[0].signers();
+        assert_eq!(signers, BTreeSet::from([1, 2, 3]));
+        let (sig, discarded) =
+            BBSPlusSignatureShare::aggregate_best_effort(shares.clone(), &signers).unwrap();
+        assert_eq!(discarded, vec![2, 4]);
+        sig.verify(&messages, public_key, params).unwrap();
+
+        // Dropping a share of the session gives no signature even with a threshold number of shares
+        let session_share = shares.remove(3);
+        assert_eq!(session_share.id, 3);
+        let mut other_session = session_share.clone();
+        other_session.e += Fr::from(1u64);
+        shares.push(other_session);
+        assert!(matches!(
+            BBSPlusSignatureShare::aggregate_best_effort(shares, &signers),
+            Err(BBSPlusError::NotEnoughConsistentShares(2, 3))
+        ));
+        assert!(matches!(
+            BBSPlusSignatureShare::<Bls12_381>::aggregate_best_effort(vec![], &signers),
+            Err(BBSPlusError::NotEnoughConsistentShares(0, 3))
+        ));
+    }
+
+    #[test]
+    fn zero_aggregate_u() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 2;
+        let message_count = 4;
+        let (params, _, mut phase1_outs, phase2_outs) =
+            setup_and_do_phase1_and_phase2(&mut rng, threshold_signers, 3, 1, message_count);
+
+        let messages = (0..message_count)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let mut shares = (0..threshold_signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new(
+                    &messages,
+                    0,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // A malicious signer picks its `u` to cancel the `u` of the other signer
+        shares[1].u = -shares[0].u;
+        assert!(matches!(
+            BBSPlusSignatureShare::aggregate(shares.clone()),
+            Err(BBSPlusError::ZeroAggregateU)
+        ));
+        let mut aggregator = Aggregator::new();
+        for share in shares {
+            aggregator.add_share(share).unwrap();
+        }
+        assert!(matches!(
+            aggregator.finalize(),
+            Err(BBSPlusError::ZeroAggregateU)
+        ));
+    }
+
+    #[test]
+    fn batch_signature_shares() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let sig_batch_size = 3;
+        let message_count = 4;
+        let (params, public_key, mut phase1_outs, phase2_outs) = setup_and_do_phase1_and_phase2(
+            &mut rng,
+            threshold_signers,
+            5,
+            sig_batch_size,
+            message_count,
+        );
+        let mut phase1_outs_copy = phase1_outs.clone();
+
+        let messages_per_sig = (0..sig_batch_size)
+            .map(|_| {
+                (0..message_count)
+                    .map(|_| Fr::rand(&mut rng))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let batch_shares = (0..threshold_signers as usize)
+            .map(|i| {
+                BBSPlusSignatureShare::new_batch(
+                    &messages_per_sig,
+                    &mut phase1_outs[i],
+                    &phase2_outs[i],
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // Shares grouped by signature
+        let shares_per_sig = (0..sig_batch_size as usize)
+            .map(|k| {
+                batch_shares
+                    .iter()
+                    .map(|shares| shares[k].clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let sigs = BBSPlusSignatureShare::aggregate_batch(shares_per_sig).unwrap();
+        assert_eq!(sigs.len(), sig_batch_size as usize);
+
+        for (k, messages) in messages_per_sig.iter().enumerate() {
+            let shares = create_shares(
+                messages,
+                k,
+                threshold_signers,
+                &mut phase1_outs_copy,
+                &phase2_outs,
+                &params,
+            );
+            for i in 0..threshold_signers as usize {
+                assert_eq!(batch_shares[i][k], shares[i]);
+            }
+            let (sum_R, sum_u, e, s) =
+                BBSPlusSignatureShare::aggregate_parts(shares.clone()).unwrap();
+            assert_eq!((sum_R * sum_u.inverse().unwrap()).into_affine(), sigs[k].A);
+            assert_eq!((e, s), (sigs[k].e, sigs[k].s));
+            let sig = BBSPlusSignatureShare::aggregate(shares).unwrap();
+            sig.verify(messages, public_key.clone(), params.clone())
+                .unwrap();
+            assert_eq!(sigs[k], sig);
+        }
+
+        // The randomness of the batch has been used
+        assert!(matches!(
+            BBSPlusSignatureShare::new_batch(
+                &messages_per_sig,
+                &mut phase1_outs[0],
+                &phase2_outs[0],
+                &params,
+            ),
+            Err(BBSPlusError::NonceReused(0))
+        ));
+    }
+
+    #[test]
+    fn signing_in_g2() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let threshold_signers = 3;
+        let total_signers = 5;
+        let sig_batch_size = 2;
+        let message_count = 4;
+        let (sk, sk_shares) =
+            trusted_party_keygen::<_, Fr>(&mut rng, threshold_signers, total_signers);
+        let params = SignatureParamsG2::<Bls12_381>::generate_using_rng(&mut rng, message_count);
+        let public_key = PublicKeyG1::generate_using_secret_key(&SecretKey(sk), &params);
+
+        let gadget_vector = GadgetVector::<Fr, KAPPA, STATISTICAL_SECURITY_PARAMETER>::new::<
+            Blake2b512,
+        >(OTE_PARAMS, b"test-gadget-vector");
+        let base_ot_outputs = do_pairwise_base_ot::<BASE_OT_KEY_SIZE>(
+            &mut rng,
+            OTE_PARAMS.num_base_ot(),
+            total_signers,
+            (1..=total_signers).collect(),
+        );
+        let mut phase1_outs = do_phase1_rounds(
+            &mut rng,
+            threshold_signers,
+            sig_batch_size,
+            b"test".to_vec(),
+        )
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| {
+            p.finish_for_bbs_plus_g2::<Blake2b512>(&sk_shares[i])
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+        let phase2_outs = do_phase2(
+            &mut rng,
+            threshold_signers,
+            &gadget_vector,
+            &base_ot_outputs,
+            &phase1_outs,
+        );
+
+        for k in 0..sig_batch_size as usize {
+            let messages = (0..message_count)
+                .map(|_| Fr::rand(&mut rng))
+                .collect::<Vec<_>>();
+            let shares = (0..threshold_signers as usize)
+                .map(|i| {
+                    BBSPlusSignatureShareG2::new(
+                        &messages,
+                        k,
+                        &mut phase1_outs[i],
+                        &phase2_outs[i],
+                        &params,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let sig = BBSPlusSignatureShareG2::aggregate(shares).unwrap();
+            sig.verify(&messages, &public_key, &params).unwrap();
+        }
+    }
 }
@@ -1,3 +1,6 @@
+//! Helpers shared by the phase 3 (signing) of threshold BBS and BBS+ which can also be used to build other
+//! threshold signatures on top of the randomness generation and multiplication phases.
+
 use crate::threshold::multiplication_phase::Phase2Output;
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
@@ -36,6 +39,18 @@ pub fn compute_masked_arguments_to_multiply<F: PrimeField>(
     Ok((masked_signing_key_shares, masked_rs))
 }
 
+/// Computes a signer's share `(R, u)` of a signature from the outputs of phase 1 and 2 where
+///     - `base` is the group element the signature is computed over, like `commitment + b` in BBS+ where `b`
+///       is computed from the uncommitted messages and `s`
+///     - `r`, `e`, `masked_r` and `masked_signing_key_share` are this signer's values at `index_in_output`
+///       of the batch in its phase 1 output
+///     - `phase2` is the signer's output of the multiplication phase. It must come from the same batch as the
+///       phase 1 output.
+///
+/// Returns `R = base * r` and `u = masked_r * (e + masked_signing_key_share) + sum of cross products at index_in_output`.
+/// Summing the `u`s of all signers gives `r * (e + sk)` since the masks cancel out.
+///
+/// Panics if `index_in_output` is not less than the batch size of `phase2`.
 pub fn compute_R_and_u<G: AffineRepr>(
     base: G::Group,
     r: &G::ScalarField,
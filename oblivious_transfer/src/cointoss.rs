@@ -40,7 +40,7 @@ impl<F: PrimeField, const SALT_SIZE: usize> Party<F, SALT_SIZE> {
     /// Creates randomness, commits to it and returns the commitments to be sent to the other parties.
     /// The randomness will serve as a share to the joint randomness. `batch_size` is the number of
     /// random values generated.
-    pub fn commit<R: RngCore>(
+    pub fn commit<R: RngCore + ?Sized>(
         rng: &mut R,
         id: ParticipantId,
         batch_size: u32,
@@ -35,7 +35,7 @@ impl<F: PrimeField, const SALT_SIZE: usize> Party<F, SALT_SIZE> {
     /// `\sum_{i}(b_{i}) = 0` and `\sum_{i}(c_{i}) = 0`.
     /// The returned map contains the commitments to be sent to the party with id as the corresponding
     /// key of the map.
-    pub fn init<R: RngCore>(
+    pub fn init<R: RngCore + ?Sized>(
         rng: &mut R,
         id: ParticipantId,
         batch_size: u32,
@@ -1,15 +1,42 @@
 use crate::{
+    encryption::{Ciphertext, Encryption},
+    error::SaverError,
+    keygen::EncryptionKey,
     setup::ChunkedCommitmentGens,
-    utils::{chunks_count, decompose},
+    utils::{
+        bytes_to_field_elements, chunks_count, chunks_count_for_radix, decompose,
+        decompose_with_radix, try_chunks_count, validate_radix, RadixPowers, CHUNK_TYPE,
+    },
 };
-use ark_ec::{AffineRepr, CurveGroup, Group, VariableBaseMSM};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group, VariableBaseMSM};
 use ark_ff::{Field, One, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{vec, vec::Vec};
+use ark_std::{
+    cfg_into_iter, cfg_iter,
+    ops::{Add, AddAssign},
+    rand::RngCore,
+    vec,
+    vec::Vec,
+    UniformRand,
+};
+use digest::Digest;
+use schnorr_pok::{compute_random_oracle_challenge, SchnorrCommitment, SchnorrResponse};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use dock_crypto_utils::{msm::multiply_field_elems_with_same_group_elem, serde_utils::*};
+use dock_crypto_utils::{
+    affine_group_element_from_byte_slices, msm::multiply_field_elems_with_same_group_elem,
+    serde_utils::*,
+};
+
+#[cfg(feature = "constant-time")]
+use crate::utils::decompose_ct;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Domain separation label for deriving the blinding of a chunked commitment from the encryption randomness
+pub const DERIVED_BLINDING_LABEL: &[u8] = b"SAVER-chunked-commitment-derived-blinding";
 
 /// Getting a commitment to the message as a single field element from commitment to its b-ary decomposition.
 ///
@@ -44,7 +71,181 @@ pub struct ChunkedCommitment<G: AffineRepr>(
     #[serde_as(as = "Vec<ArkObjectBytes>")] pub Vec<G>,
 );
 
+/// Commitment key of the chunked commitment, i.e. the `g`s and `h` created by `ChunkedCommitment::commitment_key`
+/// or `ChunkedCommitment::commitment_key_with_radix`. Create it once and reuse it for several commitments.
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct ChunkedCommitmentKey<G: AffineRepr> {
+    pub chunk_bit_size: u8,
+    /// Radix of the decomposition. `None` when the radix is `2^chunk_bit_size`
+    pub radix: Option<u16>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub key: Vec<G>,
+}
+
+impl<G: AffineRepr> ChunkedCommitmentKey<G> {
+    /// Create the key for committing to chunks of `chunk_bit_size` bits with radix `2^chunk_bit_size`. Returns
+    /// an error if `chunk_bit_size` is not one of `SUPPORTED_CHUNK_BIT_SIZES`.
+    pub fn new(gens: &ChunkedCommitmentGens<G>, chunk_bit_size: u8) -> crate::Result<Self> {
+        try_chunks_count::<G::ScalarField>(chunk_bit_size)?;
+        Ok(Self {
+            chunk_bit_size,
+            radix: None,
+            key: ChunkedCommitment::commitment_key(gens, chunk_bit_size),
+        })
+    }
+
+    /// Create the key for committing to chunks in base `radix` where `radix <= 2^chunk_bit_size`
+    pub fn new_with_radix(
+        gens: &ChunkedCommitmentGens<G>,
+        chunk_bit_size: u8,
+        radix: u16,
+    ) -> crate::Result<Self> {
+        validate_radix(chunk_bit_size, radix)?;
+        Ok(Self {
+            chunk_bit_size,
+            radix: Some(radix),
+            key: ChunkedCommitment::commitment_key_with_radix(gens, radix)?,
+        })
+    }
+
+    /// Same as `Self::new_with_radix` but takes the powers of the radix from `radix_powers` instead of computing
+    /// them again. `radix_powers` must be for `chunks_count_for_radix` chunks of its radix, else
+    /// `SaverError::IncompatibleRadixPowers` is returned.
+    pub fn new_with_radix_powers(
+        gens: &ChunkedCommitmentGens<G>,
+        chunk_bit_size: u8,
+        radix_powers: &RadixPowers<G::ScalarField>,
+    ) -> crate::Result<Self> {
+        let radix = radix_powers.radix();
+        validate_radix(chunk_bit_size, radix)?;
+        let chunks = chunks_count_for_radix::<G::ScalarField>(radix)? as usize;
+        if radix_powers.len() != chunks {
+            return Err(SaverError::IncompatibleRadixPowers(
+                radix_powers.len(),
+                chunks,
+            ));
+        }
+        let mut key = G::Group::normalize_batch(&multiply_field_elems_with_same_group_elem(
+            gens.G.into_group(),
+            radix_powers.powers(),
+        ));
+        key.push(gens.H);
+        Ok(Self {
+            chunk_bit_size,
+            radix: Some(radix),
+            key,
+        })
+    }
+
+    /// Decompose `message` and commit to its chunks and `blinding` using this key
+    pub fn commit(
+        &self,
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+    ) -> crate::Result<ChunkedCommitment<G>> {
+        let decomposed = self.values_to_commit(message, blinding)?;
+        Ok(ChunkedCommitment(
+            G::Group::msm_bigint(&self.key, &decomposed).into_affine(),
+            self.key.clone(),
+        ))
+    }
+
+    /// Powers of the radix by which the chunks are multiplied to get the message, i.e.
+    /// `radix^{n-1}, ..., radix, 1` for `n` chunks. Errors if the key doesn't have generators for at
+    /// least one chunk and the blinding, as can happen with a deserialized key
+    fn chunk_weights(&self) -> crate::Result<Vec<G::ScalarField>> {
+        if self.key.len() < 2 {
+            return Err(SaverError::VectorShorterThanExpected(self.key.len(), 2));
+        }
+        let radix = G::ScalarField::from(
+            self.radix
+                .map(|r| r as u64)
+                .unwrap_or(1 << self.chunk_bit_size),
+        );
+        let mut weights = vec![G::ScalarField::one(); self.key.len() - 1];
+        for i in (0..weights.len().saturating_sub(1)).rev() {
+            weights[i] = weights[i + 1] * radix;
+        }
+        Ok(weights)
+    }
+
+    fn values_to_commit(
+        &self,
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+    ) -> crate::Result<Vec<<G::ScalarField as PrimeField>::BigInt>> {
+        match self.radix {
+            None => {
+                ChunkedCommitment::<G>::get_values_to_commit(message, blinding, self.chunk_bit_size)
+            }
+            Some(radix) => ChunkedCommitment::<G>::get_values_to_commit_with_radix(
+                message,
+                blinding,
+                self.chunk_bit_size,
+                radix,
+            ),
+        }
+    }
+}
+
+/// Proof that a chunked commitment `J = m_1*G_1 + m_2*G_2 + ... + m_n*G_n + r'*H` and a Pedersen commitment
+/// `C = m*G' + r''*H'` commit to the same message `m = m_1*{b^{n-1}} + m_2*{b^{n-2}} + ... + m_n`. This is a Schnorr
+/// proof of knowledge of the openings of `J` and `C` where the response for `m` isn't sent but computed by the
+/// verifier from the responses for the chunks. The challenge is created using Fiat-Shamir.
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct ChunkedCommitmentEqualityProof<G: AffineRepr> {
+    /// Commitment to the randomness of the chunks and `r'`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t_chunked: G,
+    /// Commitment to the randomness of `m` and `r''`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t_pedersen: G,
+    /// Responses for the chunks and `r'`
+    #[serde(bound = "SchnorrResponse<G>: Serialize, for<'a> SchnorrResponse<G>: Deserialize<'a>")]
+    pub response_chunked: SchnorrResponse<G>,
+    /// Response for `r''`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub response_blinding: G::ScalarField,
+}
+
+/// Proof that the commitment `psi` in a ciphertext and a chunked commitment `J` commit to the same chunks, i.e.
+/// `psi = m_1*Y_1 + m_2*Y_2 + ... + m_n*Y_n + r*P_1` and `J = m_1*G_1 + m_2*G_2 + ... + m_n*G_n + r'*H`. This is a
+/// Schnorr proof of knowledge of the openings of `psi` and `J` where the responses for the chunks are shared.
+/// The challenge is created using Fiat-Shamir.
+#[serde_as]
+#[derive(
+    Clone, PartialEq, Eq, Debug, CanonicalSerialize, CanonicalDeserialize, Serialize, Deserialize,
+)]
+pub struct EncryptionConsistencyProof<G: AffineRepr> {
+    /// Commitment to the randomness of the chunks and `r`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t_ciphertext: G,
+    /// Commitment to the randomness of the chunks and `r'`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t_chunked: G,
+    /// Responses for the chunks and `r`
+    #[serde(bound = "SchnorrResponse<G>: Serialize, for<'a> SchnorrResponse<G>: Deserialize<'a>")]
+    pub response_ciphertext: SchnorrResponse<G>,
+    /// Response for `r'`
+    #[serde_as(as = "ArkObjectBytes")]
+    pub response_blinding: G::ScalarField,
+}
+
 impl<G: AffineRepr> ChunkedCommitment<G> {
+    /// Derive the generators `(G, H)` from `seed` by hashing it to the group using try-and-increment, so no one
+    /// knows the discrete log of `H` wrt `G` which is necessary for the commitment to be binding. This is the same
+    /// as `ChunkedCommitmentGens::new`.
+    pub fn generators_from_seed<D: Digest>(seed: &[u8]) -> (G, G) {
+        let gens = ChunkedCommitmentGens::<G>::new::<D>(seed);
+        (gens.G, gens.H)
+    }
+
     /// Decompose a given field element `message` to `chunks_count` chunks each of size `chunk_bit_size` and
     /// create a Pedersen commitment to those chunks. say `m` is decomposed as `m_1`, `m_2`, .. `m_n`.
     /// Create commitment key as multiples of `g` as `g_n, g_{n-1}, ..., g_2, g_1` using `Self::commitment_key`. Now commit as `m_1 * g_1 + m_2 * g_2 + ... + m_n * g_n + r * h`
@@ -55,14 +256,456 @@ impl<G: AffineRepr> ChunkedCommitment<G> {
         chunk_bit_size: u8,
         gens: &ChunkedCommitmentGens<G>,
     ) -> crate::Result<Self> {
-        let decomposed = Self::get_values_to_commit(message, blinding, chunk_bit_size)?;
-        let gs = Self::commitment_key(gens, chunk_bit_size);
+        ChunkedCommitmentKey::new(gens, chunk_bit_size)?.commit(message, blinding)
+    }
+
+    /// Similar to `Self::new` but decomposes `message` in base `radix` which need not be a power of 2. Each
+    /// chunk must fit in `chunk_bit_size` bits, i.e. `radix <= 2^chunk_bit_size`. The commitment key is created
+    /// using `Self::commitment_key_with_radix`.
+    pub fn new_with_radix(
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        chunk_bit_size: u8,
+        radix: u16,
+        gens: &ChunkedCommitmentGens<G>,
+    ) -> crate::Result<Self> {
+        ChunkedCommitmentKey::new_with_radix(gens, chunk_bit_size, radix)?.commit(message, blinding)
+    }
+
+    /// Commit to the big-endian integer `bytes` reduced modulo the order of the scalar field, i.e. the message is
+    /// `G::ScalarField::from_be_bytes_mod_order(bytes)`. So when `bytes` is less than the modulus, the message is
+    /// that integer but larger values wrap around and different byte strings can give the same message. Returns
+    /// `SaverError::BytesTooLongForField` if `bytes` is longer than the byte size of the modulus unless `wrapping`
+    /// is true. Note that byte strings of the same length as the modulus can still be larger than it and wrap around.
+    pub fn new_from_bytes_reduced(
+        bytes: &[u8],
+        blinding: &G::ScalarField,
+        chunk_bit_size: u8,
+        gens: &ChunkedCommitmentGens<G>,
+        wrapping: bool,
+    ) -> crate::Result<Self> {
+        let field_byte_size = (G::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(8);
+        if !wrapping && bytes.len() > field_byte_size {
+            return Err(SaverError::BytesTooLongForField(
+                bytes.len(),
+                field_byte_size,
+            ));
+        }
+        let message = G::ScalarField::from_be_bytes_mod_order(bytes);
+        Self::new(&message, blinding, chunk_bit_size, gens)
+    }
+
+    /// Same as `Self::new_with_radix` but uses the cached `comm_key` instead of creating the commitment key again.
+    /// So when creating many commitments with the same radix, create the key once, like with
+    /// `ChunkedCommitmentKey::new_with_radix_powers`, and pass it to each call.
+    pub fn new_with_cache(
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        comm_key: &ChunkedCommitmentKey<G>,
+    ) -> crate::Result<Self> {
+        comm_key.commit(message, blinding)
+    }
+
+    /// Same as `Self::new` but the chunk bit size is the one used by the encryption key `ek` so that the chunks of
+    /// this commitment are the same as the chunks encrypted using `ek`. Returns an error if the number of chunks
+    /// supported by `ek` isn't the `chunks_count` of any supported chunk bit size.
+    pub fn new_matching_encryption<E: Pairing<G1Affine = G>>(
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        ek: &EncryptionKey<E>,
+        gens: &ChunkedCommitmentGens<G>,
+    ) -> crate::Result<Self> {
+        let (chunk_bit_size, _, _) = ek.chunk_params()?;
+        Self::new(message, blinding, chunk_bit_size, gens)
+    }
+
+    /// Same as `Self::new` but the blinding `r'` isn't chosen by the caller but derived from the randomness `r`
+    /// returned by `Encryption::encrypt` as `r' = k*r` where `k = H(DERIVED_BLINDING_LABEL || g || h)` is given by
+    /// `Self::blinding_derivation_factor`. As `k` only depends on the public generators, anyone can compute it and
+    /// the relation `r' = k*r` is linear so a single proof over the ciphertext and this commitment can use the
+    /// response for `r` scaled by `k` as the response for `r'`. Returns the commitment and `r'`.
+    pub fn new_with_derived_blinding<D: Digest>(
+        message: &G::ScalarField,
+        r: &G::ScalarField,
+        chunk_bit_size: u8,
+        g: &G,
+        h: &G,
+    ) -> crate::Result<(Self, G::ScalarField)> {
+        let blinding = Self::blinding_derivation_factor::<D>(g, h)? * r;
+        let gens = ChunkedCommitmentGens { G: *g, H: *h };
+        Ok((
+            Self::new(message, &blinding, chunk_bit_size, &gens)?,
+            blinding,
+        ))
+    }
+
+    /// The factor `k` used by `Self::new_with_derived_blinding` to derive the blinding `r' = k*r`. It's the hash of
+    /// `DERIVED_BLINDING_LABEL`, `g` and `h` to a field element.
+    pub fn blinding_derivation_factor<D: Digest>(g: &G, h: &G) -> crate::Result<G::ScalarField> {
+        let mut bytes = DERIVED_BLINDING_LABEL.to_vec();
+        g.serialize_compressed(&mut bytes)
+            .and_then(|_| h.serialize_compressed(&mut bytes))
+            .map_err(|_| SaverError::ChallengeSerialization)?;
+        Ok(compute_random_oracle_challenge::<G::ScalarField, D>(&bytes))
+    }
+
+    /// Create chunked commitments to several messages where `messages[i]` is committed with `blindings[i]`.
+    /// The commitment key is created only once and shared by all the commitments.
+    pub fn new_batch(
+        messages: &[G::ScalarField],
+        blindings: &[G::ScalarField],
+        chunk_bit_size: u8,
+        gens: &ChunkedCommitmentGens<G>,
+    ) -> crate::Result<Vec<Self>> {
+        if messages.len() != blindings.len() {
+            return Err(SaverError::UnequalNoOfMessagesAndBlindings(
+                messages.len(),
+                blindings.len(),
+            ));
+        }
+        let comm_key = ChunkedCommitmentKey::new(gens, chunk_bit_size)?;
+        cfg_iter!(messages)
+            .zip(cfg_iter!(blindings))
+            .map(|(message, blinding)| comm_key.commit(message, blinding))
+            .collect()
+    }
+
+    /// Create a single chunked commitment to all of `messages` with one `blinding`. Message `messages[j]` uses its
+    /// own generator `G[j]` which must be different for each message, otherwise the commitment is only to the sum of
+    /// those messages. The commitment key is the concatenation of the `g`s created by `Self::commitment_key` from
+    /// each `G[j]`, in the order of the messages, followed by `H`, i.e.
+    /// `[G[0]_n, ..., G[0]_1, G[1]_n, ..., G[1]_1, ..., H]`. So this is same as the sum of commitments to each
+    /// `messages[j]` using generators `G[j]` and `H` when their blindings sum to `blinding`.
+    pub fn new_for_message_vector(
+        messages: &[G::ScalarField],
+        blinding: &G::ScalarField,
+        chunk_bit_size: u8,
+        G: &[G],
+        H: &G,
+    ) -> crate::Result<Self> {
+        if messages.len() != G.len() {
+            return Err(SaverError::UnequalNoOfMessagesAndGenerators(
+                messages.len(),
+                G.len(),
+            ));
+        }
+        let mut comm_key = Vec::new();
+        let mut scalars = Vec::new();
+        for (message, G) in messages.iter().zip(G.iter()) {
+            let gens = ChunkedCommitmentGens { G: *G, H: *H };
+            let mut ck = Self::commitment_key(&gens, chunk_bit_size);
+            // Remove `H`
+            ck.pop();
+            comm_key.append(&mut ck);
+            scalars.append(
+                &mut decompose(message, chunk_bit_size)?
+                    .into_iter()
+                    .map(|m| <G::ScalarField as PrimeField>::BigInt::from(m as u64))
+                    .collect(),
+            );
+        }
+        comm_key.push(*H);
+        scalars.push(blinding.into_bigint());
         Ok(Self(
-            G::Group::msm_bigint(&gs, &decomposed).into_affine(),
-            gs,
+            G::Group::msm_bigint(&comm_key, &scalars).into_affine(),
+            comm_key,
         ))
     }
 
+    /// Create a single chunked commitment to the byte string `data` with one `blinding`. `data` is packed in field
+    /// elements using `bytes_to_field_elements`, i.e. `0x80` and then 0 bytes are appended to `data` and each group of
+    /// `bytes_per_field_element` bytes is a big-endian field element. These field elements are committed using
+    /// `Self::new_for_message_vector` so `G` must have `field_elements_count_for_bytes(data.len())` generators
+    /// and the commitment key has the layout described there.
+    pub fn new_for_bytes(
+        data: &[u8],
+        blinding: &G::ScalarField,
+        chunk_bit_size: u8,
+        G: &[G],
+        H: &G,
+    ) -> crate::Result<Self> {
+        Self::new_for_message_vector(
+            &bytes_to_field_elements::<G::ScalarField>(data),
+            blinding,
+            chunk_bit_size,
+            G,
+            H,
+        )
+    }
+
+    /// Encrypt `message` using `Encryption::encrypt`, create a chunked commitment to it with `blinding` and prove
+    /// that the commitment `psi` in the ciphertext and the chunked commitment commit to the same chunks. So the
+    /// chunked commitment, which is `message*G + blinding*H`, commits to the encrypted message. Verify the proof
+    /// with `Self::verify_encryption_consistency`. The ciphertext still needs to be verified on its own.
+    pub fn prove_encryption_consistency<
+        R: RngCore,
+        E: Pairing<G1Affine = G, ScalarField = G::ScalarField>,
+        D: Digest,
+    >(
+        rng: &mut R,
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        ek: &EncryptionKey<E>,
+        g_i: &[G],
+        gens: &ChunkedCommitmentGens<G>,
+        chunk_bit_size: u8,
+    ) -> crate::Result<(Ciphertext<E>, Self, EncryptionConsistencyProof<G>)> {
+        let (ct, r) = Encryption::encrypt(rng, message, ek, g_i, chunk_bit_size)?;
+        let (chunks, r) = ct.commitment_opening(message, &r, ek, chunk_bit_size)?;
+        let comm = Self::new(message, blinding, chunk_bit_size, gens)?;
+        let n = chunks.len();
+
+        let mut witnesses = chunks
+            .into_iter()
+            .map(|c| G::ScalarField::from(c as u64))
+            .collect::<Vec<_>>();
+        witnesses.push(r);
+        // The chunks have the same randomness in both commitments
+        let mut blindings = (0..=n)
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let ct_comm_key = ek.commitment_key();
+        let t_ciphertext = SchnorrCommitment::new(&ct_comm_key, blindings.clone());
+        blindings[n] = G::ScalarField::rand(rng);
+        let t_chunked = SchnorrCommitment::new(&comm.1, blindings);
+        let challenge = comm.encryption_consistency_challenge::<D>(
+            &ct_comm_key,
+            &ct.commitment,
+            &t_ciphertext.t,
+            &t_chunked.t,
+        )?;
+        let response_ciphertext = t_ciphertext
+            .response(&witnesses, &challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)?;
+        witnesses[n] = *blinding;
+        let response_chunked = t_chunked
+            .response(&witnesses, &challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)?;
+        let proof = EncryptionConsistencyProof {
+            t_ciphertext: t_ciphertext.t,
+            t_chunked: t_chunked.t,
+            response_ciphertext,
+            response_blinding: response_chunked.0[n],
+        };
+        Ok((ct, comm, proof))
+    }
+
+    /// Verify a proof created by `Self::prove_encryption_consistency` that the commitment in `ct` and this
+    /// commitment, created with `gens` and `chunk_bit_size`, commit to the same chunks
+    pub fn verify_encryption_consistency<
+        E: Pairing<G1Affine = G, ScalarField = G::ScalarField>,
+        D: Digest,
+    >(
+        &self,
+        ct: &Ciphertext<E>,
+        proof: &EncryptionConsistencyProof<G>,
+        ek: &EncryptionKey<E>,
+        gens: &ChunkedCommitmentGens<G>,
+        chunk_bit_size: u8,
+    ) -> crate::Result<()> {
+        if self.1 != Self::commitment_key(gens, chunk_bit_size) {
+            return Err(SaverError::InvalidCommitment);
+        }
+        let ct_comm_key = ek.commitment_key();
+        let n = self.1.len() - 1;
+        if ct_comm_key.len() != n + 1 || proof.response_ciphertext.len() != n + 1 {
+            return Err(SaverError::InvalidProof);
+        }
+        let challenge = self.encryption_consistency_challenge::<D>(
+            &ct_comm_key,
+            &ct.commitment,
+            &proof.t_ciphertext,
+            &proof.t_chunked,
+        )?;
+        proof
+            .response_ciphertext
+            .is_valid(&ct_comm_key, &ct.commitment, &proof.t_ciphertext, challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)?;
+        let mut response_chunked = proof.response_ciphertext.0[..n].to_vec();
+        response_chunked.push(proof.response_blinding);
+        SchnorrResponse(response_chunked)
+            .is_valid(&self.1, &self.0, &proof.t_chunked, challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)
+    }
+
+    fn encryption_consistency_challenge<D: Digest>(
+        &self,
+        ct_comm_key: &[G],
+        ct_commitment: &G,
+        t_ciphertext: &G,
+        t_chunked: &G,
+    ) -> crate::Result<G::ScalarField> {
+        let mut bytes = vec![];
+        self.serialize_compressed(&mut bytes)
+            .and_then(|_| ct_comm_key.serialize_compressed(&mut bytes))
+            .and_then(|_| ct_commitment.serialize_compressed(&mut bytes))
+            .and_then(|_| t_ciphertext.serialize_compressed(&mut bytes))
+            .and_then(|_| t_chunked.serialize_compressed(&mut bytes))
+            .map_err(|_| SaverError::ChallengeSerialization)?;
+        Ok(compute_random_oracle_challenge::<G::ScalarField, D>(&bytes))
+    }
+
+    /// Prove that this commitment, created with `comm_key` from `message` and `blinding`, and the Pedersen commitment
+    /// `other_gens.G * message + other_gens.H * other_blinding` commit to the same message. Returns
+    /// `SaverError::InvalidCommitment` if this commitment wasn't created with `comm_key`.
+    pub fn prove_equal_to_pedersen<R: RngCore, D: Digest>(
+        &self,
+        rng: &mut R,
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        comm_key: &ChunkedCommitmentKey<G>,
+        other_gens: &ChunkedCommitmentGens<G>,
+        other_blinding: &G::ScalarField,
+    ) -> crate::Result<ChunkedCommitmentEqualityProof<G>> {
+        if self.1 != comm_key.key {
+            return Err(SaverError::InvalidCommitment);
+        }
+        let weights = comm_key.chunk_weights()?;
+        let witnesses = comm_key
+            .values_to_commit(message, blinding)?
+            .into_iter()
+            .map(|v| G::ScalarField::from_bigint(v).unwrap())
+            .collect::<Vec<_>>();
+        let blindings = (0..witnesses.len())
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        // Randomness for `m` is the same combination of the randomness of the chunks as `m` is of the chunks
+        let message_blinding = weights
+            .iter()
+            .zip(blindings.iter())
+            .map(|(w, b)| *w * b)
+            .sum::<G::ScalarField>();
+        let t_chunked = SchnorrCommitment::new(&comm_key.key, blindings);
+        let other_bases = [other_gens.G, other_gens.H];
+        let t_pedersen = SchnorrCommitment::new(
+            &other_bases,
+            vec![message_blinding, G::ScalarField::rand(rng)],
+        );
+        let other_commitment =
+            (other_gens.G * message + other_gens.H * other_blinding).into_affine();
+        let challenge = self.equality_challenge::<D>(
+            comm_key,
+            other_gens,
+            &other_commitment,
+            &t_chunked.t,
+            &t_pedersen.t,
+        )?;
+        let response_chunked = t_chunked
+            .response(&witnesses, &challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)?;
+        let response_pedersen = t_pedersen
+            .response(&[*message, *other_blinding], &challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)?;
+        Ok(ChunkedCommitmentEqualityProof {
+            t_chunked: t_chunked.t,
+            t_pedersen: t_pedersen.t,
+            response_chunked,
+            response_blinding: response_pedersen.0[1],
+        })
+    }
+
+    /// Verify a proof created by `Self::prove_equal_to_pedersen` that this commitment, created with `comm_key`, and
+    /// `other_commitment`, created with `other_gens`, commit to the same message
+    pub fn verify_equal<D: Digest>(
+        &self,
+        proof: &ChunkedCommitmentEqualityProof<G>,
+        comm_key: &ChunkedCommitmentKey<G>,
+        other_gens: &ChunkedCommitmentGens<G>,
+        other_commitment: &G,
+    ) -> crate::Result<()> {
+        if self.1 != comm_key.key {
+            return Err(SaverError::InvalidCommitment);
+        }
+        let weights = comm_key.chunk_weights()?;
+        if proof.response_chunked.len() != weights.len() + 1 {
+            return Err(SaverError::InvalidProof);
+        }
+        let challenge = self.equality_challenge::<D>(
+            comm_key,
+            other_gens,
+            other_commitment,
+            &proof.t_chunked,
+            &proof.t_pedersen,
+        )?;
+        proof
+            .response_chunked
+            .is_valid(&comm_key.key, &self.0, &proof.t_chunked, challenge)
+            .map_err(|_| SaverError::SchnorrProofFailed)?;
+        let message_response = weights
+            .iter()
+            .zip(proof.response_chunked.0.iter())
+            .map(|(w, s)| *w * s)
+            .sum::<G::ScalarField>();
+        SchnorrResponse(vec![message_response, proof.response_blinding])
+            .is_valid(
+                &[other_gens.G, other_gens.H],
+                other_commitment,
+                &proof.t_pedersen,
+                challenge,
+            )
+            .map_err(|_| SaverError::SchnorrProofFailed)
+    }
+
+    fn equality_challenge<D: Digest>(
+        &self,
+        comm_key: &ChunkedCommitmentKey<G>,
+        other_gens: &ChunkedCommitmentGens<G>,
+        other_commitment: &G,
+        t_chunked: &G,
+        t_pedersen: &G,
+    ) -> crate::Result<G::ScalarField> {
+        let mut bytes = vec![];
+        comm_key
+            .serialize_compressed(&mut bytes)
+            .and_then(|_| self.0.serialize_compressed(&mut bytes))
+            .and_then(|_| other_gens.G.serialize_compressed(&mut bytes))
+            .and_then(|_| other_gens.H.serialize_compressed(&mut bytes))
+            .and_then(|_| other_commitment.serialize_compressed(&mut bytes))
+            .and_then(|_| t_chunked.serialize_compressed(&mut bytes))
+            .and_then(|_| t_pedersen.serialize_compressed(&mut bytes))
+            .map_err(|_| SaverError::ChallengeSerialization)?;
+        Ok(compute_random_oracle_challenge::<G::ScalarField, D>(&bytes))
+    }
+
+    /// Update the commitment when the chunk at `chunk_index` of the committed message changes from `old_value` to
+    /// `new_value` by adding `(new_value - old_value) * g_i` where `g_i = radix^{n-1-i} * g` is the `i`-th element
+    /// of the commitment key, `n` being the number of chunks. The blinding is unchanged. The caller must ensure that
+    /// `new_value` is less than the radix.
+    pub fn update_chunk(
+        &mut self,
+        chunk_index: usize,
+        old_value: CHUNK_TYPE,
+        new_value: CHUNK_TYPE,
+    ) -> crate::Result<()> {
+        let chunks = self.1.len().saturating_sub(1);
+        if chunk_index >= chunks {
+            return Err(SaverError::ChunkIndexOutOfBounds(chunk_index, chunks));
+        }
+        let diff = G::ScalarField::from(new_value as u64) - G::ScalarField::from(old_value as u64);
+        self.0 = (self.0 + self.1[chunk_index] * diff).into_affine();
+        Ok(())
+    }
+
+    /// Check that this is a commitment to `message` with `blinding` by recreating the commitment and its key.
+    /// `radix` is the radix of the decomposition and `None` means `2^chunk_bit_size` as in `Self::new`.
+    pub fn verify_opening(
+        &self,
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        chunk_bit_size: u8,
+        radix: Option<u16>,
+        gens: &ChunkedCommitmentGens<G>,
+    ) -> bool {
+        let comm_key = match radix {
+            None => ChunkedCommitmentKey::new(gens, chunk_bit_size),
+            Some(radix) => ChunkedCommitmentKey::new_with_radix(gens, chunk_bit_size, radix),
+        };
+        match comm_key.and_then(|k| k.commit(message, blinding)) {
+            Ok(comm) => comm == *self,
+            Err(_) => false,
+        }
+    }
+
     /// Similar to `Self::new` but expects the commitment key to be created already. Returns the commitment.
     pub fn get_commitment_given_commitment_key(
         message: &G::ScalarField,
@@ -74,11 +717,59 @@ impl<G: AffineRepr> ChunkedCommitment<G> {
         Ok(G::Group::msm_bigint(comm_key, &decomposed).into_affine())
     }
 
+    /// Length of the commitment key created by `Self::commitment_key`, i.e. `chunks_count` `g`s and 1 `h`
+    pub fn key_len(chunk_bit_size: u8) -> usize {
+        chunks_count::<G::ScalarField>(chunk_bit_size) as usize + 1
+    }
+
     /// Commitment key (vector of all `g`s and `h`) for the chunked commitment
     /// Given a group element `g`, create `chunks_count` multiples of `g` as `g_n, g_{n-1}, ..., g_2, g_1` where each `g_i = {radix^i} * g` and `radix = 2^chunk_bit_ize`
     pub fn commitment_key(gens: &ChunkedCommitmentGens<G>, chunk_bit_size: u8) -> Vec<G> {
         let radix = (1 << chunk_bit_size) as u64;
         let chunks = chunks_count::<G::ScalarField>(chunk_bit_size);
+        Self::commitment_key_given_chunks_count(gens, chunks, radix)
+    }
+
+    /// Same as `Self::commitment_key` but the `g`s are in ascending order of the powers of radix, i.e.
+    /// `g, radix * g, ..., radix^{chunks_count - 1} * g` followed by `h`. This pairs with the little-endian
+    /// decomposition, i.e. the reverse of `decompose`, whereas `Self::commitment_key` pairs with `decompose`.
+    pub fn commitment_key_ascending(gens: &ChunkedCommitmentGens<G>, chunk_bit_size: u8) -> Vec<G> {
+        let mut ck = Self::commitment_key(gens, chunk_bit_size);
+        let chunks = ck.len() - 1;
+        ck[..chunks].reverse();
+        ck
+    }
+
+    /// Commitment key of `chunks_count` `g`s followed by `h` where each element is created by hashing `seed || i`
+    /// to the group for its position `i`, so no relation between any 2 of them is known. This changes the
+    /// homomorphic structure of the commitment as the `g`s aren't multiples of a single `g` anymore, i.e.
+    /// `m_1 * g_1 + ... + m_n * g_n` isn't a commitment to `m = m_1*{radix^{n-1}} + ... + m_n`. So a commitment with
+    /// this key only hides the chunks and can't be transformed to a commitment to the message or used in proofs
+    /// like `Self::prove_equal_to_pedersen` which rely on that.
+    pub fn independent_commitment_key<D: Digest>(seed: &[u8], chunk_bit_size: u8) -> Vec<G> {
+        (0..Self::key_len(chunk_bit_size) as u32)
+            .map(|i| affine_group_element_from_byte_slices!(seed, i.to_le_bytes()))
+            .collect()
+    }
+
+    /// Same as `Self::commitment_key` but for an arbitrary `radix` and thus `chunks_count_for_radix` multiples of `g`
+    pub fn commitment_key_with_radix(
+        gens: &ChunkedCommitmentGens<G>,
+        radix: u16,
+    ) -> crate::Result<Vec<G>> {
+        let chunks = chunks_count_for_radix::<G::ScalarField>(radix)?;
+        Ok(Self::commitment_key_given_chunks_count(
+            gens,
+            chunks,
+            radix as u64,
+        ))
+    }
+
+    fn commitment_key_given_chunks_count(
+        gens: &ChunkedCommitmentGens<G>,
+        chunks: u8,
+        radix: u64,
+    ) -> Vec<G> {
         let gs = if radix.is_power_of_two() {
             Self::commitment_key_for_radix_power_of_2(gens.G.into_group(), chunks, radix)
         } else {
@@ -94,7 +785,25 @@ impl<G: AffineRepr> ChunkedCommitment<G> {
         blinding: &G::ScalarField,
         chunk_bit_size: u8,
     ) -> crate::Result<Vec<<G::ScalarField as PrimeField>::BigInt>> {
-        let mut decomposed = decompose(message, chunk_bit_size)?
+        #[cfg(feature = "constant-time")]
+        let decomposed = decompose_ct(message, chunk_bit_size)?;
+        #[cfg(not(feature = "constant-time"))]
+        let decomposed = decompose(message, chunk_bit_size)?;
+        let mut decomposed = decomposed
+            .into_iter()
+            .map(|m| <G::ScalarField as PrimeField>::BigInt::from(m as u64))
+            .collect::<Vec<_>>();
+        decomposed.push(blinding.into_bigint());
+        Ok(decomposed)
+    }
+
+    fn get_values_to_commit_with_radix(
+        message: &G::ScalarField,
+        blinding: &G::ScalarField,
+        chunk_bit_size: u8,
+        radix: u16,
+    ) -> crate::Result<Vec<<G::ScalarField as PrimeField>::BigInt>> {
+        let mut decomposed = decompose_with_radix(message, chunk_bit_size, radix)?
             .into_iter()
             .map(|m| <G::ScalarField as PrimeField>::BigInt::from(m as u64))
             .collect::<Vec<_>>();
@@ -102,6 +811,7 @@ impl<G: AffineRepr> ChunkedCommitment<G> {
         Ok(decomposed)
     }
 
+    /// Create `g * radix^{chunks_count - 1}, ..., g * radix, g` by repeated doublings. Used when `radix` is a power of 2
     fn commitment_key_for_radix_power_of_2(
         g: G::Group,
         chunks_count: u8,
@@ -122,6 +832,10 @@ impl<G: AffineRepr> ChunkedCommitment<G> {
         gs
     }
 
+    /// Create `g * radix^{chunks_count - 1}, ..., g * radix, g` by computing the powers of `radix` and multiplying
+    /// `g` with them using a window table of `g`. Works for any `radix`, including powers of 2. With feature `parallel`,
+    /// the powers and the multiplications are computed in parallel, but creating the window table still makes
+    /// this slower than the doubling in `Self::commitment_key_for_radix_power_of_2` for the supported chunk counts.
     fn commitment_key_for_radix_non_power_of_2(
         g: G::Group,
         chunks_count: u8,
@@ -129,25 +843,49 @@ impl<G: AffineRepr> ChunkedCommitment<G> {
     ) -> Vec<G::Group> {
         let radix = G::ScalarField::from(radix);
         // factors = [radix^{chunks_count - 1}, radix^{chunks_count - 2}, ..., 1]
-        let mut factors = vec![];
-        for i in 1..chunks_count {
-            factors.push(radix.pow([(chunks_count - i) as u64]));
-        }
-        factors.push(G::ScalarField::one());
+        let factors = cfg_into_iter!(1..=chunks_count)
+            .map(|i| {
+                if i == chunks_count {
+                    G::ScalarField::one()
+                } else {
+                    radix.pow([(chunks_count - i) as u64])
+                }
+            })
+            .collect::<Vec<_>>();
         multiply_field_elems_with_same_group_elem(g, &factors)
     }
 }
 
+/// Adding commitments to `m_1` and `m_2` with blindings `r_1` and `r_2` gives a commitment to `m_1 + m_2` with
+/// blinding `r_1 + r_2`. Panics if the commitments have different commitment keys.
+impl<G: AffineRepr> Add for ChunkedCommitment<G> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Panics if the commitments have different commitment keys.
+impl<G: AffineRepr> AddAssign for ChunkedCommitment<G> {
+    fn add_assign(&mut self, rhs: Self) {
+        assert_eq!(
+            self.1, rhs.1,
+            "Cannot add chunked commitments with different commitment keys"
+        );
+        self.0 = (self.0 + rhs.0).into_affine();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use ark_bls12_381::{Bls12_381, Fr};
-    use ark_ec::pairing::Pairing;
 
     use ark_std::{
         collections::BTreeSet,
-        ops::Add,
         rand::{prelude::StdRng, SeedableRng},
         UniformRand,
     };
@@ -171,18 +909,25 @@ mod tests {
             let mut rng = StdRng::seed_from_u64(0u64);
             let g = <Bls12_381 as Pairing>::G1::rand(&mut rng);
             let chunks_count = chunks_count::<Fr>(chunk_bit_size);
+            println!(
+                "Creating commitment keys with feature parallel enabled: {}",
+                cfg!(feature = "parallel")
+            );
 
             let start = Instant::now();
             let gs_1 = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::commitment_key_for_radix_power_of_2(g, chunks_count, 1 << chunk_bit_size);
             println!(
-                "commitment_key_for_radix_power_of_2 time {:?}",
+                "commitment_key_for_radix_power_of_2 time for {} chunks {:?}",
+                chunks_count,
                 start.elapsed()
             );
 
+            // Uses a window table
             let start = Instant::now();
             let gs_2 = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::commitment_key_for_radix_non_power_of_2(g, chunks_count, 1 << chunk_bit_size);
             println!(
-                "commitment_key_for_radix_non_power_of_2 time {:?}",
+                "commitment_key_for_radix_non_power_of_2 time for {} chunks {:?}",
+                chunks_count,
                 start.elapsed()
             );
 
@@ -193,6 +938,850 @@ mod tests {
         check(16);
     }
 
+    #[test]
+    fn ascending_commitment_key() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let ck = ChunkedCommitment::commitment_key(&gens, chunk_bit_size);
+            let ck_asc = ChunkedCommitment::commitment_key_ascending(&gens, chunk_bit_size);
+            let n = chunks_count::<Fr>(chunk_bit_size) as usize;
+            assert_eq!(ck_asc.len(), n + 1);
+            assert_eq!(ck_asc[0], gens.G);
+            assert_eq!(ck_asc[n], gens.H);
+            let radix = Fr::from(1u64 << chunk_bit_size);
+            assert_eq!(ck_asc[1], (gens.G * radix).into_affine());
+
+            // The little-endian decomposition with the ascending key gives the same commitment
+            let m = Fr::rand(&mut rng);
+            let blinding = Fr::rand(&mut rng);
+            let mut chunks = decompose(&m, chunk_bit_size)
+                .unwrap()
+                .into_iter()
+                .map(|c| Fr::from(c as u64))
+                .collect::<Vec<_>>();
+            chunks.push(blinding);
+            let comm = <Bls12_381 as Pairing>::G1::msm_unchecked(&ck, &chunks);
+            chunks[..n].reverse();
+            let comm_asc = <Bls12_381 as Pairing>::G1::msm_unchecked(&ck_asc, &chunks);
+            assert_eq!(comm, comm_asc);
+            assert_eq!(
+                comm.into_affine(),
+                ChunkedCommitment::new(&m, &blinding, chunk_bit_size, &gens)
+                    .unwrap()
+                    .0
+            );
+        }
+    }
+
+    #[test]
+    fn generators_from_seed() {
+        let (g, h) = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::generators_from_seed::<
+            Blake2b512,
+        >(b"test");
+        assert_ne!(g, h);
+        assert!(!g.is_zero() && !h.is_zero());
+        assert_eq!(
+            (g, h),
+            ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::generators_from_seed::<Blake2b512>(
+                b"test"
+            )
+        );
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new::<Blake2b512>(b"test");
+        assert_eq!((gens.G, gens.H), (g, h));
+        let (g_1, h_1) =
+            ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::generators_from_seed::<Blake2b512>(
+                b"test1",
+            );
+        assert_ne!(g, g_1);
+        assert_ne!(h, h_1);
+    }
+
+    #[test]
+    fn independent_commitment_key() {
+        for chunk_bit_size in [4, 8] {
+            let ck =
+                ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::independent_commitment_key::<
+                    Blake2b512,
+                >(b"test", chunk_bit_size);
+            assert_eq!(
+                ck.len(),
+                ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::key_len(chunk_bit_size)
+            );
+            assert_eq!(
+                ck,
+                ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::independent_commitment_key::<
+                    Blake2b512,
+                >(b"test", chunk_bit_size)
+            );
+            assert_ne!(
+                ck,
+                ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::independent_commitment_key::<
+                    Blake2b512,
+                >(b"test1", chunk_bit_size)
+            );
+            let radix = Fr::from(1u64 << chunk_bit_size);
+            for i in 0..ck.len() {
+                assert!(!ck[i].is_zero());
+                for j in i + 1..ck.len() {
+                    assert_ne!(ck[i], ck[j]);
+                    // Not related by powers of radix like the key created by `commitment_key`
+                    assert_ne!(ck[i], (ck[j] * radix.pow([(j - i) as u64])).into_affine());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn commitment_with_radix() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        for _ in 0..10 {
+            let m = Fr::rand(&mut rng);
+            let blinding = Fr::rand(&mut rng);
+            let expected = gens
+                .G
+                .mul_bigint(m.into_bigint())
+                .add(&(gens.H.mul_bigint(blinding.into_bigint())))
+                .into_affine();
+
+            // Power of 2 radix gives the same commitment and key as `ChunkedCommitment::new`
+            for chunk_bit_size in [4, 8] {
+                let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_with_radix(
+                    &m,
+                    &blinding,
+                    chunk_bit_size,
+                    1 << chunk_bit_size,
+                    &gens,
+                )
+                .unwrap();
+                assert_eq!(
+                    comm,
+                    ChunkedCommitment::new(&m, &blinding, chunk_bit_size, &gens).unwrap()
+                );
+                assert_eq!(comm.0, expected);
+            }
+
+            for (chunk_bit_size, radix) in [(4, 10), (8, 200), (16, 1000)] {
+                let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_with_radix(
+                    &m,
+                    &blinding,
+                    chunk_bit_size,
+                    radix,
+                    &gens,
+                )
+                .unwrap();
+                assert_eq!(comm.0, expected);
+                assert_eq!(
+                    comm.1.len(),
+                    chunks_count_for_radix::<Fr>(radix).unwrap() as usize + 1
+                );
+            }
+        }
+
+        // Chunks of radix 17 don't fit in 4 bits
+        assert!(
+            ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_with_radix(
+                &Fr::rand(&mut rng),
+                &Fr::rand(&mut rng),
+                4,
+                17,
+                &gens,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn commitment_with_cached_radix_powers() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        for (chunk_bit_size, radix) in [(4, 10), (4, 16), (8, 200), (16, 1000)] {
+            let powers = RadixPowers::<Fr>::for_field(radix).unwrap();
+            let comm_key =
+                ChunkedCommitmentKey::new_with_radix_powers(&gens, chunk_bit_size, &powers)
+                    .unwrap();
+            assert_eq!(
+                comm_key,
+                ChunkedCommitmentKey::new_with_radix(&gens, chunk_bit_size, radix).unwrap()
+            );
+            for _ in 0..5 {
+                let m = Fr::rand(&mut rng);
+                let blinding = Fr::rand(&mut rng);
+                let comm = ChunkedCommitment::new_with_cache(&m, &blinding, &comm_key).unwrap();
+                assert_eq!(
+                    comm,
+                    ChunkedCommitment::new_with_radix(&m, &blinding, chunk_bit_size, radix, &gens)
+                        .unwrap()
+                );
+                assert_eq!(comm.0, (gens.G * m + gens.H * blinding).into_affine());
+            }
+        }
+
+        // Powers for fewer chunks than needed
+        let powers = RadixPowers::<Fr>::new(10, 5).unwrap();
+        assert!(matches!(
+            ChunkedCommitmentKey::new_with_radix_powers(&gens, 4, &powers),
+            Err(SaverError::IncompatibleRadixPowers(5, _))
+        ));
+        // Chunks of radix 17 don't fit in 4 bits
+        let powers = RadixPowers::<Fr>::for_field(17).unwrap();
+        assert!(ChunkedCommitmentKey::new_with_radix_powers(&gens, 4, &powers).is_err());
+    }
+
+    #[test]
+    fn commitment_from_bytes_reduced() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+        let chunk_bit_size = 8;
+
+        let bytes = [7u8; 32];
+        let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_from_bytes_reduced(
+            &bytes,
+            &blinding,
+            chunk_bit_size,
+            &gens,
+            false,
+        )
+        .unwrap();
+        let m = Fr::from_be_bytes_mod_order(&bytes);
+        assert_eq!(
+            comm,
+            ChunkedCommitment::new(&m, &blinding, chunk_bit_size, &gens).unwrap()
+        );
+
+        // 32 bytes larger than the modulus wrap around
+        let max = [255u8; 32];
+        let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_from_bytes_reduced(
+            &max,
+            &blinding,
+            chunk_bit_size,
+            &gens,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            comm.0,
+            (gens.G * Fr::from_be_bytes_mod_order(&max) + gens.H * blinding).into_affine()
+        );
+
+        // Longer than the modulus is only accepted with wrapping
+        let long = [1u8; 40];
+        assert!(matches!(
+            ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_from_bytes_reduced(
+                &long,
+                &blinding,
+                chunk_bit_size,
+                &gens,
+                false,
+            ),
+            Err(SaverError::BytesTooLongForField(40, 32))
+        ));
+        let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_from_bytes_reduced(
+            &long,
+            &blinding,
+            chunk_bit_size,
+            &gens,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            comm.0,
+            (gens.G * Fr::from_be_bytes_mod_order(&long) + gens.H * blinding).into_affine()
+        );
+    }
+
+    #[test]
+    fn batch_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let count = 20;
+        let messages = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let blindings = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        for chunk_bit_size in [4, 8, 16] {
+            let start = Instant::now();
+            let comms = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_batch(
+                &messages,
+                &blindings,
+                chunk_bit_size,
+                &gens,
+            )
+            .unwrap();
+            let batch_time = start.elapsed();
+
+            let start = Instant::now();
+            for i in 0..count {
+                let comm =
+                    ChunkedCommitment::new(&messages[i], &blindings[i], chunk_bit_size, &gens)
+                        .unwrap();
+                assert_eq!(comms[i], comm);
+            }
+            println!(
+                "For {} commitments with chunk size {}, batch took {:?} and one by one took {:?}",
+                count,
+                chunk_bit_size,
+                batch_time,
+                start.elapsed()
+            );
+        }
+
+        assert!(
+            ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_batch(
+                &messages,
+                &blindings[1..],
+                4,
+                &gens,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn reuse_commitment_key() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let comm_key = ChunkedCommitmentKey::new(&gens, 8).unwrap();
+        let comm_key_with_radix = ChunkedCommitmentKey::new_with_radix(&gens, 8, 200).unwrap();
+        assert_eq!(comm_key.key, ChunkedCommitment::commitment_key(&gens, 8));
+        assert!(ChunkedCommitmentKey::new_with_radix(&gens, 4, 200).is_err());
+        for b in [0, 5, 32] {
+            assert!(matches!(
+                ChunkedCommitmentKey::new(&gens, b),
+                Err(SaverError::UnexpectedBase(c)) if c == b
+            ));
+        }
+        test_serialization!(
+            ChunkedCommitmentKey<<Bls12_381 as Pairing>::G1Affine>,
+            comm_key_with_radix
+        );
+
+        for _ in 0..10 {
+            let m = Fr::rand(&mut rng);
+            let blinding = Fr::rand(&mut rng);
+            assert_eq!(
+                comm_key.commit(&m, &blinding).unwrap(),
+                ChunkedCommitment::new(&m, &blinding, 8, &gens).unwrap()
+            );
+            assert_eq!(
+                comm_key_with_radix.commit(&m, &blinding).unwrap(),
+                ChunkedCommitment::new_with_radix(&m, &blinding, 8, 200, &gens).unwrap()
+            );
+            assert_eq!(
+                comm_key.commit(&m, &blinding).unwrap().0,
+                comm_key_with_radix.commit(&m, &blinding).unwrap().0
+            );
+        }
+    }
+
+    #[test]
+    fn commitment_in_g2() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        type G2Affine = <Bls12_381 as Pairing>::G2Affine;
+        let g = <Bls12_381 as Pairing>::G2::rand(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let chunks_count = chunks_count::<Fr>(chunk_bit_size);
+            assert_eq!(
+                ChunkedCommitment::<G2Affine>::commitment_key_for_radix_power_of_2(
+                    g,
+                    chunks_count,
+                    1 << chunk_bit_size
+                ),
+                ChunkedCommitment::<G2Affine>::commitment_key_for_radix_non_power_of_2(
+                    g,
+                    chunks_count,
+                    1 << chunk_bit_size
+                )
+            );
+        }
+
+        let gens = ChunkedCommitmentGens::<G2Affine>::new_using_rng(&mut rng);
+        let m = Fr::rand(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+        let expected = gens
+            .G
+            .mul_bigint(m.into_bigint())
+            .add(&(gens.H.mul_bigint(blinding.into_bigint())))
+            .into_affine();
+        for chunk_bit_size in [4, 8, 16] {
+            let comm =
+                ChunkedCommitment::<G2Affine>::new(&m, &blinding, chunk_bit_size, &gens).unwrap();
+            assert_eq!(comm.0, expected);
+        }
+        let comm =
+            ChunkedCommitment::<G2Affine>::new_with_radix(&m, &blinding, 8, 200, &gens).unwrap();
+        assert_eq!(comm.0, expected);
+    }
+
+    #[test]
+    fn commitment_serialization() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new(
+            &Fr::rand(&mut rng),
+            &Fr::rand(&mut rng),
+            8,
+            &gens,
+        )
+        .unwrap();
+        test_serialization!(ChunkedCommitment<<Bls12_381 as Pairing>::G1Affine>, comm);
+    }
+
+    #[test]
+    fn commitment_opening() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let m = Fr::rand(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+
+        let comm =
+            ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new(&m, &blinding, 8, &gens)
+                .unwrap();
+        assert!(comm.verify_opening(&m, &blinding, 8, None, &gens));
+        assert!(comm.verify_opening(&m, &blinding, 8, Some(256), &gens));
+        assert!(!comm.verify_opening(&m, &Fr::rand(&mut rng), 8, None, &gens));
+        assert!(!comm.verify_opening(&Fr::rand(&mut rng), &blinding, 8, None, &gens));
+        // Same commitment but different key
+        assert!(!comm.verify_opening(&m, &blinding, 4, None, &gens));
+
+        let comm = ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::new_with_radix(
+            &m, &blinding, 8, 200, &gens,
+        )
+        .unwrap();
+        assert!(comm.verify_opening(&m, &blinding, 8, Some(200), &gens));
+        assert!(!comm.verify_opening(&m, &blinding, 8, None, &gens));
+        assert!(!comm.verify_opening(&m, &blinding, 4, Some(200), &gens));
+    }
+
+    #[test]
+    fn commitment_to_message_vector() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let count = 3;
+        let messages = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let blindings = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let G = (0..count)
+            .map(|_| <Bls12_381 as Pairing>::G1Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let H = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let blinding = blindings.iter().sum::<Fr>();
+
+        for chunk_bit_size in [4, 8, 16] {
+            let comm = ChunkedCommitment::new_for_message_vector(
+                &messages,
+                &blinding,
+                chunk_bit_size,
+                &G,
+                &H,
+            )
+            .unwrap();
+            let chunks = chunks_count::<Fr>(chunk_bit_size) as usize;
+            assert_eq!(comm.1.len(), count * chunks + 1);
+
+            let mut sum = <Bls12_381 as Pairing>::G1::default();
+            for j in 0..count {
+                let gens = ChunkedCommitmentGens { G: G[j], H };
+                let c = ChunkedCommitment::new(&messages[j], &blindings[j], chunk_bit_size, &gens)
+                    .unwrap();
+                // The bases for each message are at its position in the commitment key
+                assert_eq!(comm.1[j * chunks..(j + 1) * chunks], c.1[..chunks]);
+                sum += c.0;
+            }
+            assert_eq!(comm.0, sum.into_affine());
+        }
+
+        assert!(
+            ChunkedCommitment::new_for_message_vector(&messages, &blinding, 8, &G[1..], &H)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn commitment_to_bytes() {
+        use crate::utils::{compose, field_elements_count_for_bytes, field_elements_to_bytes};
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let data = (0..100).map(|_| u8::rand(&mut rng)).collect::<Vec<_>>();
+        let count = field_elements_count_for_bytes::<Fr>(data.len());
+        let G = (0..count)
+            .map(|_| <Bls12_381 as Pairing>::G1Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let H = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+
+        for chunk_bit_size in [4, 8, 16] {
+            let comm =
+                ChunkedCommitment::new_for_bytes(&data, &blinding, chunk_bit_size, &G, &H).unwrap();
+            let messages = bytes_to_field_elements::<Fr>(&data);
+            assert_eq!(
+                comm,
+                ChunkedCommitment::new_for_message_vector(
+                    &messages,
+                    &blinding,
+                    chunk_bit_size,
+                    &G,
+                    &H
+                )
+                .unwrap()
+            );
+
+            // Recompose the byte string from the committed chunks
+            let recomposed = messages
+                .iter()
+                .map(|m| compose::<Fr>(&decompose(m, chunk_bit_size).unwrap(), chunk_bit_size))
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(field_elements_to_bytes(&recomposed).unwrap(), data);
+        }
+
+        assert!(ChunkedCommitment::new_for_bytes(&data, &blinding, 8, &G[1..], &H).is_err());
+    }
+
+    #[test]
+    fn equality_with_pedersen_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let other_gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let m = Fr::rand(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+        let other_blinding = Fr::rand(&mut rng);
+        let other_comm = (other_gens.G * m + other_gens.H * other_blinding).into_affine();
+
+        let keys = vec![
+            ChunkedCommitmentKey::new(&gens, 4).unwrap(),
+            ChunkedCommitmentKey::new(&gens, 8).unwrap(),
+            ChunkedCommitmentKey::new(&gens, 16).unwrap(),
+            ChunkedCommitmentKey::new_with_radix(&gens, 8, 200).unwrap(),
+        ];
+        for comm_key in keys {
+            let comm = comm_key.commit(&m, &blinding).unwrap();
+            let proof = comm
+                .prove_equal_to_pedersen::<_, Blake2b512>(
+                    &mut rng,
+                    &m,
+                    &blinding,
+                    &comm_key,
+                    &other_gens,
+                    &other_blinding,
+                )
+                .unwrap();
+            comm.verify_equal::<Blake2b512>(&proof, &comm_key, &other_gens, &other_comm)
+                .unwrap();
+            test_serialization!(
+                ChunkedCommitmentEqualityProof<<Bls12_381 as Pairing>::G1Affine>,
+                proof
+            );
+
+            // Pedersen commitment to a different message
+            let wrong_comm =
+                (other_gens.G * (m + Fr::from(1u64)) + other_gens.H * other_blinding).into_affine();
+            assert!(comm
+                .verify_equal::<Blake2b512>(&proof, &comm_key, &other_gens, &wrong_comm)
+                .is_err());
+
+            // Proof created for a different message
+            let m2 = Fr::rand(&mut rng);
+            let comm2 = comm_key.commit(&m2, &blinding).unwrap();
+            let proof2 = comm2
+                .prove_equal_to_pedersen::<_, Blake2b512>(
+                    &mut rng,
+                    &m2,
+                    &blinding,
+                    &comm_key,
+                    &other_gens,
+                    &other_blinding,
+                )
+                .unwrap();
+            assert!(comm
+                .verify_equal::<Blake2b512>(&proof2, &comm_key, &other_gens, &other_comm)
+                .is_err());
+
+            let mut tampered = proof.clone();
+            tampered.response_blinding += Fr::from(1u64);
+            assert!(matches!(
+                comm.verify_equal::<Blake2b512>(&tampered, &comm_key, &other_gens, &other_comm),
+                Err(SaverError::SchnorrProofFailed)
+            ));
+            // The error can be cloned like the other errors of this crate
+            let err = comm
+                .verify_equal::<Blake2b512>(&tampered, &comm_key, &other_gens, &other_comm)
+                .unwrap_err();
+            assert!(matches!(err.clone(), SaverError::SchnorrProofFailed));
+        }
+
+        // Key different from the one of the commitment
+        let comm = ChunkedCommitment::new(&m, &blinding, 8, &gens).unwrap();
+        assert!(matches!(
+            comm.prove_equal_to_pedersen::<_, Blake2b512>(
+                &mut rng,
+                &m,
+                &blinding,
+                &ChunkedCommitmentKey::new(&gens, 4).unwrap(),
+                &other_gens,
+                &other_blinding,
+            ),
+            Err(SaverError::InvalidCommitment)
+        ));
+
+        // Deserialized key without any generators
+        let comm_key = ChunkedCommitmentKey::new(&gens, 8).unwrap();
+        let proof = comm
+            .prove_equal_to_pedersen::<_, Blake2b512>(
+                &mut rng,
+                &m,
+                &blinding,
+                &comm_key,
+                &other_gens,
+                &other_blinding,
+            )
+            .unwrap();
+        let empty_key = ChunkedCommitmentKey {
+            chunk_bit_size: 8,
+            radix: None,
+            key: vec![],
+        };
+        let empty_comm = ChunkedCommitment(comm.0, vec![]);
+        assert!(matches!(
+            empty_comm.verify_equal::<Blake2b512>(&proof, &empty_key, &other_gens, &other_comm),
+            Err(SaverError::VectorShorterThanExpected(0, 2))
+        ));
+        assert!(matches!(
+            empty_comm.prove_equal_to_pedersen::<_, Blake2b512>(
+                &mut rng,
+                &m,
+                &blinding,
+                &empty_key,
+                &other_gens,
+                &other_blinding,
+            ),
+            Err(SaverError::VectorShorterThanExpected(0, 2))
+        ));
+    }
+
+    #[test]
+    fn update_chunk() {
+        use crate::utils::compose;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let m = Fr::rand(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let mut chunks = decompose(&m, chunk_bit_size).unwrap();
+            let mut comm = ChunkedCommitment::new(&m, &blinding, chunk_bit_size, &gens).unwrap();
+            for (i, new_value) in [(0, 1), (chunks.len() / 2, 0), (chunks.len() - 1, 7)] {
+                comm.update_chunk(i, chunks[i], new_value).unwrap();
+                chunks[i] = new_value;
+                let updated = compose::<Fr>(&chunks, chunk_bit_size).unwrap();
+                assert_eq!(
+                    comm,
+                    ChunkedCommitment::new(&updated, &blinding, chunk_bit_size, &gens).unwrap()
+                );
+            }
+            assert!(matches!(
+                comm.update_chunk(chunks.len(), 0, 1),
+                Err(SaverError::ChunkIndexOutOfBounds(i, n)) if i == chunks.len() && n == chunks.len()
+            ));
+        }
+    }
+
+    #[test]
+    fn encryption_consistency() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let (_, g_i, _, ek, _) = enc_setup(chunk_bit_size, &mut rng);
+            let m = Fr::rand(&mut rng);
+            let blinding = Fr::rand(&mut rng);
+            let (ct, comm, proof) = ChunkedCommitment::prove_encryption_consistency::<
+                _,
+                _,
+                Blake2b512,
+            >(
+                &mut rng, &m, &blinding, &ek, &g_i, &gens, chunk_bit_size
+            )
+            .unwrap();
+            assert_eq!(comm.0, (gens.G * m + gens.H * blinding).into_affine());
+            comm.verify_encryption_consistency::<_, Blake2b512>(
+                &ct,
+                &proof,
+                &ek,
+                &gens,
+                chunk_bit_size,
+            )
+            .unwrap();
+            test_serialization!(
+                EncryptionConsistencyProof<<Bls12_381 as Pairing>::G1Affine>,
+                proof
+            );
+
+            // Ciphertext of another message
+            let other_m = Fr::rand(&mut rng);
+            let (other_ct, _) =
+                Encryption::encrypt(&mut rng, &other_m, &ek, &g_i, chunk_bit_size).unwrap();
+            assert!(comm
+                .verify_encryption_consistency::<_, Blake2b512>(
+                    &other_ct,
+                    &proof,
+                    &ek,
+                    &gens,
+                    chunk_bit_size,
+                )
+                .is_err());
+
+            // Commitment to another message
+            let other_comm =
+                ChunkedCommitment::new(&Fr::rand(&mut rng), &blinding, chunk_bit_size, &gens)
+                    .unwrap();
+            assert!(other_comm
+                .verify_encryption_consistency::<_, Blake2b512>(
+                    &ct,
+                    &proof,
+                    &ek,
+                    &gens,
+                    chunk_bit_size,
+                )
+                .is_err());
+
+            let mut tampered = proof.clone();
+            tampered.response_ciphertext.0[0] += Fr::from(1u64);
+            assert!(comm
+                .verify_encryption_consistency::<_, Blake2b512>(
+                    &ct,
+                    &tampered,
+                    &ek,
+                    &gens,
+                    chunk_bit_size,
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn commitment_matching_encryption() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let m = Fr::rand(&mut rng);
+        let blinding = Fr::rand(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let (_, _, _, mut ek, _) = enc_setup(chunk_bit_size, &mut rng);
+            assert_eq!(
+                ChunkedCommitment::new_matching_encryption(&m, &blinding, &ek, &gens).unwrap(),
+                ChunkedCommitment::new(&m, &blinding, chunk_bit_size, &gens).unwrap()
+            );
+
+            // Key with a chunk count that no chunk bit size gives
+            ek.X.pop();
+            ek.Y.pop();
+            ek.Z.pop();
+            assert!(matches!(
+                ChunkedCommitment::new_matching_encryption(&m, &blinding, &ek, &gens),
+                Err(SaverError::NoChunkBitSizeForChunksCount(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn commitment_with_derived_blinding() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let other_gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let (_, g_i, _, ek, _) = enc_setup(chunk_bit_size, &mut rng);
+            let m = Fr::rand(&mut rng);
+            let (ct, r) = Encryption::encrypt(&mut rng, &m, &ek, &g_i, chunk_bit_size).unwrap();
+            let (_, r) = ct.commitment_opening(&m, &r, &ek, chunk_bit_size).unwrap();
+            let (comm, blinding) = ChunkedCommitment::new_with_derived_blinding::<Blake2b512>(
+                &m,
+                &r,
+                chunk_bit_size,
+                &gens.G,
+                &gens.H,
+            )
+            .unwrap();
+
+            // Derivation is deterministic and the relation with `r` can be computed from public values
+            let k = ChunkedCommitment::blinding_derivation_factor::<Blake2b512>(&gens.G, &gens.H)
+                .unwrap();
+            assert_eq!(blinding, k * r);
+            assert_eq!(
+                ChunkedCommitment::new_with_derived_blinding::<Blake2b512>(
+                    &m,
+                    &r,
+                    chunk_bit_size,
+                    &gens.G,
+                    &gens.H
+                )
+                .unwrap(),
+                (comm.clone(), blinding)
+            );
+            assert_ne!(
+                ChunkedCommitment::blinding_derivation_factor::<Blake2b512>(
+                    &other_gens.G,
+                    &other_gens.H
+                )
+                .unwrap(),
+                k
+            );
+
+            // The chunked commitment is still a commitment to the message with the derived blinding
+            assert_eq!(comm.0, (gens.G * m + gens.H * blinding).into_affine());
+            assert_eq!(
+                comm,
+                ChunkedCommitment::new(&m, &blinding, chunk_bit_size, &gens).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn add_commitments() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let m1 = Fr::rand(&mut rng);
+        let m2 = Fr::rand(&mut rng);
+        let r1 = Fr::rand(&mut rng);
+        let r2 = Fr::rand(&mut rng);
+        for chunk_bit_size in [4, 8, 16] {
+            let c1 = ChunkedCommitment::new(&m1, &r1, chunk_bit_size, &gens).unwrap();
+            let c2 = ChunkedCommitment::new(&m2, &r2, chunk_bit_size, &gens).unwrap();
+            let expected =
+                ChunkedCommitment::new(&(m1 + m2), &(r1 + r2), chunk_bit_size, &gens).unwrap();
+            assert_eq!(c1.clone() + c2.clone(), expected);
+
+            let mut c = c1;
+            c += c2;
+            assert_eq!(c, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_commitments_with_different_keys() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let gens =
+            ChunkedCommitmentGens::<<Bls12_381 as Pairing>::G1Affine>::new_using_rng(&mut rng);
+        let m = Fr::rand(&mut rng);
+        let r = Fr::rand(&mut rng);
+        let _ = ChunkedCommitment::new(&m, &r, 4, &gens).unwrap()
+            + ChunkedCommitment::new(&m, &r, 8, &gens).unwrap();
+    }
+
     #[test]
     fn commitment_transform_works() {
         fn check(chunk_bit_size: u8) {
@@ -240,12 +1829,25 @@ mod tests {
                 );
                 decomposed.push(blinding);
 
+                assert_eq!(
+                    gs.len(),
+                    ChunkedCommitment::<<Bls12_381 as Pairing>::G1Affine>::key_len(chunk_bit_size)
+                );
                 assert_eq!(gs.len(), decomposed.len());
 
                 let bases = ek.commitment_key();
 
-                let mut wit2 = decomposed.clone();
-                wit2[n as usize] = r;
+                let (chunks, ct_blinding) =
+                    ct.commitment_opening(&m, &r, &ek, chunk_bit_size).unwrap();
+                let mut wit2 = chunks
+                    .into_iter()
+                    .map(|c| Fr::from(c as u64))
+                    .collect::<Vec<_>>();
+                wit2.push(ct_blinding);
+                assert_eq!(wit2[..n], decomposed[..n]);
+                assert!(ct
+                    .commitment_opening(&m, &blinding, &ek, chunk_bit_size)
+                    .is_err());
 
                 let start = Instant::now();
                 let mut statements = Statements::<Bls12_381>::new();
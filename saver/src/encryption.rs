@@ -109,6 +109,32 @@ macro_rules! impl_enc_funcs {
             )
         }
 
+        /// Return the opening of `self.commitment` using the commitment key `ek.commitment_key()`, i.e. the chunks of
+        /// `message` followed by the randomness `r` returned by the encryption. The chunks are the witnesses shared
+        /// with a `ChunkedCommitment` to `message`. Returns `SaverError::InvalidCommitment` if these don't open the
+        /// commitment.
+        pub fn commitment_opening(
+            &self,
+            message: &E::ScalarField,
+            r: &E::ScalarField,
+            ek: &EncryptionKey<E>,
+            chunk_bit_size: u8,
+        ) -> crate::Result<(Vec<CHUNK_TYPE>, E::ScalarField)> {
+            let decomposed = utils::decompose(message, chunk_bit_size)?;
+            let mut scalars = decomposed
+                .iter()
+                .map(|m_i| <E::ScalarField as PrimeField>::BigInt::from(*m_i as u64))
+                .collect::<Vec<_>>();
+            scalars.push(r.into_bigint());
+            let ck = ek.commitment_key();
+            if ck.len() != scalars.len()
+                || E::G1::msm_bigint(&ck, &scalars).into_affine() != self.commitment
+            {
+                return Err(SaverError::InvalidCommitment);
+            }
+            Ok((decomposed, *r))
+        }
+
         /// Verify that the decrypted message corresponds to original plaintext in the ciphertext
         pub fn verify_decryption(
             &self,
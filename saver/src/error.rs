@@ -4,6 +4,8 @@ use legogroth16::error::Error as LegoGroth16Error;
 #[derive(Clone, Debug)]
 pub enum SaverError {
     UnexpectedBase(u8),
+    /// The radix is less than 2 or its chunks don't fit in the given chunk bit size
+    InvalidRadix(u16, u8),
     InvalidDecomposition,
     LegoGroth16Error(LegoGroth16Error),
     SynthesisError(SynthesisError),
@@ -18,6 +20,24 @@ pub enum SaverError {
     CouldNotFindDiscreteLog,
     InvalidPairingPowers,
     PairingCheckFailed,
+    UnequalNoOfMessagesAndBlindings(usize, usize),
+    UnequalNoOfMessagesAndGenerators(usize, usize),
+    /// None of the supported chunk bit sizes gives this many chunks
+    NoChunkBitSizeForChunksCount(u8),
+    /// The field elements aren't the output of `bytes_to_field_elements`
+    InvalidBytePadding,
+    /// Index of the chunk is not less than the number of chunks
+    ChunkIndexOutOfBounds(usize, usize),
+    /// The message has more non-zero chunks than this number of chunks
+    MessageTooLargeForChunks(u8),
+    /// The radix powers are for this many chunks but this many chunks were needed
+    IncompatibleRadixPowers(usize, usize),
+    /// The byte string is longer than this many bytes of a field element
+    BytesTooLongForField(usize, usize),
+    /// Creating or verifying the responses of a Schnorr proof failed
+    SchnorrProofFailed,
+    /// Serializing the values hashed to create a challenge failed
+    ChallengeSerialization,
 }
 
 impl From<SynthesisError> for SaverError {
@@ -12,7 +12,12 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use crate::{error::SaverError, saver_groth16, setup::EncryptionGens, utils::chunks_count};
+use crate::{
+    error::SaverError,
+    saver_groth16,
+    setup::EncryptionGens,
+    utils::{chunk_bit_size_for_chunks_count, chunks_count},
+};
 use dock_crypto_utils::{msm::multiply_field_elems_with_same_group_elem, serde_utils::*};
 
 /// Used to decrypt
@@ -120,6 +125,16 @@ macro_rules! impl_enc_key_funcs {
             Ok(())
         }
 
+        /// Return the chunk bit size, the radix and the number of chunks of the messages encrypted with this key
+        /// so a chunked commitment matching the encryption can be created. The radix is `2^chunk_bit_size` and
+        /// thus a `u32` as it doesn't fit in `u16` for 16-bit chunks. Returns an error if the key is malformed or
+        /// its number of chunks isn't the `chunks_count` of any supported chunk bit size.
+        pub fn chunk_params(&self) -> crate::Result<(u8, u32, usize)> {
+            let chunks = self.supported_chunks_count()?;
+            let chunk_bit_size = chunk_bit_size_for_chunks_count::<E::ScalarField>(chunks)?;
+            Ok((chunk_bit_size, 1 << chunk_bit_size, chunks as usize))
+        }
+
         pub fn commitment_key(&self) -> Vec<E::G1Affine> {
             let mut ck = self.Y.clone();
             ck.push(self.P_1.clone());
@@ -360,6 +375,14 @@ pub(crate) mod tests {
                 prepared_dk.supported_chunks_count().unwrap(),
                 chunk_count as u8
             );
+            assert_eq!(
+                ek.chunk_params().unwrap(),
+                (chunk_bit_size, 1 << chunk_bit_size, chunk_count)
+            );
+            assert_eq!(
+                prepared_ek.chunk_params().unwrap(),
+                ek.chunk_params().unwrap()
+            );
             assert_eq!(ek.commitment_key().len(), chunk_count + 1);
             assert_eq!(prepared_ek.commitment_key().len(), chunk_count + 1);
             assert_eq!(ek.commitment_key()[..chunk_count], ek.Y);
@@ -378,4 +401,28 @@ pub(crate) mod tests {
         check_keygen(8);
         check_keygen(16);
     }
+
+    #[test]
+    fn chunk_params_of_malformed_key() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let chunk_count = chunks_count::<Fr>(8) as usize;
+        let gens = EncryptionGens::<Bls12_381>::new_using_rng(&mut rng);
+        let g_i = (0..chunk_count)
+            .map(|_| <Bls12_381 as Pairing>::G1Affine::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let g_delta = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let g_gamma = <Bls12_381 as Pairing>::G1Affine::rand(&mut rng);
+        let (_, mut ek, _) = keygen(&mut rng, 8, &gens, &g_i, &g_delta, &g_gamma).unwrap();
+        ek.Y.pop();
+        assert!(matches!(
+            ek.chunk_params(),
+            Err(SaverError::MalformedEncryptionKey(_, _))
+        ));
+        ek.X.pop();
+        ek.Z.pop();
+        assert!(matches!(
+            ek.chunk_params(),
+            Err(SaverError::NoChunkBitSizeForChunksCount(_))
+        ));
+    }
 }
@@ -53,7 +53,7 @@ pub type Result<T> = core::result::Result<T, error::SaverError>;
 
 pub mod prelude {
     pub use crate::{
-        commitment::ChunkedCommitment,
+        commitment::{ChunkedCommitment, ChunkedCommitmentKey},
         error::SaverError,
         keygen::{
             keygen, DecryptionKey, EncryptionKey, PreparedDecryptionKey, PreparedEncryptionKey,
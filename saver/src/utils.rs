@@ -3,6 +3,7 @@ use ark_ff::{BigInteger, PrimeField};
 use ark_std::vec::Vec;
 
 /// Return number of chunks given the bit size of chunk. Considers the size of the field.
+/// Panics if `chunk_bit_size` is 0. Use `try_chunks_count` when `chunk_bit_size` isn't known to be valid.
 pub fn chunks_count<F: PrimeField>(chunk_bit_size: u8) -> u8 {
     let scalar_size = F::MODULUS_BIT_SIZE as usize;
     let bit_size = chunk_bit_size as usize;
@@ -12,8 +13,29 @@ pub fn chunks_count<F: PrimeField>(chunk_bit_size: u8) -> u8 {
 
 pub type CHUNK_TYPE = u16;
 
+/// Chunk bit sizes supported by `decompose` and `compose`
+pub const SUPPORTED_CHUNK_BIT_SIZES: [u8; 3] = [4, 8, 16];
+
+/// Same as `chunks_count` but returns an error if `chunk_bit_size` is not one of `SUPPORTED_CHUNK_BIT_SIZES`
+pub fn try_chunks_count<F: PrimeField>(chunk_bit_size: u8) -> crate::Result<u8> {
+    if !SUPPORTED_CHUNK_BIT_SIZES.contains(&chunk_bit_size) {
+        return Err(SaverError::UnexpectedBase(chunk_bit_size));
+    }
+    Ok(chunks_count::<F>(chunk_bit_size))
+}
+
+/// Return the supported chunk bit size that gives `chunks` many chunks for field `F`
+pub fn chunk_bit_size_for_chunks_count<F: PrimeField>(chunks: u8) -> crate::Result<u8> {
+    SUPPORTED_CHUNK_BIT_SIZES
+        .into_iter()
+        .find(|b| matches!(try_chunks_count::<F>(*b), Ok(c) if c == chunks))
+        .ok_or(SaverError::NoChunkBitSizeForChunksCount(chunks))
+}
+
 /// Given an element `F`, break it into chunks where each chunk is of `chunk_bit_size` bits. This is
-/// essentially an n-ary representation where n is `chunk_bit_size`. Returns big-endian representation.
+/// essentially an n-ary representation where n is `chunk_bit_size`. Returns big-endian representation
+/// of exactly `chunks_count` chunks.
+/// Returns an error if `chunk_bit_size` is not one of 4, 8 or 16.
 pub fn decompose<F: PrimeField>(message: &F, chunk_bit_size: u8) -> crate::Result<Vec<CHUNK_TYPE>> {
     let bytes = message.into_bigint().to_bytes_be();
     let mut decomposition = Vec::<CHUNK_TYPE>::new();
@@ -41,18 +63,133 @@ pub fn decompose<F: PrimeField>(message: &F, chunk_bit_size: u8) -> crate::Resul
         }
         b => return Err(SaverError::UnexpectedBase(b)),
     }
+    // The byte representation can have more chunks than needed for the field's size but those must be 0
+    let extra = decomposition
+        .len()
+        .saturating_sub(try_chunks_count::<F>(chunk_bit_size)? as usize);
+    if decomposition[..extra].iter().any(|c| *c != 0) {
+        return Err(SaverError::InvalidDecomposition);
+    }
+    decomposition.drain(0..extra);
+    Ok(decomposition)
+}
+
+/// Same as `decompose` but returns exactly `num_chunks` chunks, big-endian. If `num_chunks` is more than what
+/// `decompose` returns, the decomposition is left-padded with 0 chunks. If it's less, the dropped most significant
+/// chunks must be 0, else `SaverError::MessageTooLargeForChunks` is returned. Useful for messages known to be small.
+pub fn decompose_to<F: PrimeField>(
+    message: &F,
+    chunk_bit_size: u8,
+    num_chunks: u8,
+) -> crate::Result<Vec<CHUNK_TYPE>> {
+    try_chunks_count::<F>(chunk_bit_size)?;
+    let mut decomposition = decompose(message, chunk_bit_size)?;
+    let n = num_chunks as usize;
+    if n >= decomposition.len() {
+        let mut padded = ark_std::vec![0; n - decomposition.len()];
+        padded.append(&mut decomposition);
+        return Ok(padded);
+    }
+    let extra = decomposition.len() - n;
+    if decomposition[..extra].iter().any(|c| *c != 0) {
+        return Err(SaverError::MessageTooLargeForChunks(num_chunks));
+    }
+    decomposition.drain(0..extra);
+    Ok(decomposition)
+}
+
+/// Same as `decompose` but each chunk is extracted from the limbs of `message` with the same shift and mask
+/// operations irrespective of the value of `message`. Only the choice of `chunk_bit_size` decides the operations.
+/// `decompose` doesn't branch on the value of `message` either, so this doesn't protect against timing attacks
+/// any better. Note that neither is constant time as a whole: the conversion of `message` with `into_bigint` isn't
+/// guaranteed to be constant time, and committing to the chunks with `msm_bigint`, like `ChunkedCommitment` does,
+/// takes time that depends on the chunks.
+#[cfg(feature = "constant-time")]
+pub fn decompose_ct<F: PrimeField>(
+    message: &F,
+    chunk_bit_size: u8,
+) -> crate::Result<Vec<CHUNK_TYPE>> {
+    let chunks = try_chunks_count::<F>(chunk_bit_size)? as usize;
+    let bigint = message.into_bigint();
+    let limbs = bigint.as_ref();
+    let mask = (1u64 << chunk_bit_size) - 1;
+    // Supported chunk bit sizes divide 64 so a chunk never spans 2 limbs
+    let chunks_per_limb = 64 / chunk_bit_size as usize;
+    let mut decomposition = ark_std::vec![0; chunks];
+    for i in 0..chunks {
+        // `i` is the position of the chunk from the least significant end
+        let shift = (i % chunks_per_limb) * chunk_bit_size as usize;
+        decomposition[chunks - 1 - i] =
+            ((limbs[i / chunks_per_limb] >> shift) & mask) as CHUNK_TYPE;
+    }
+    Ok(decomposition)
+}
+
+/// Return number of chunks needed to represent any element of `F` in base `radix`, i.e. the number of
+/// base `radix` digits in the largest element of `F`
+pub fn chunks_count_for_radix<F: PrimeField>(radix: CHUNK_TYPE) -> crate::Result<u8> {
+    if radix < 2 {
+        return Err(SaverError::InvalidRadix(radix, 0));
+    }
+    let mut max = F::MODULUS;
+    max.sub_with_borrow(&F::BigInt::from(1u64));
+    let mut count = 0;
+    while !max.is_zero() {
+        div_rem_by_small(&mut max, radix as u64);
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Given an element `F`, break it into chunks in base `radix` where each chunk must fit in `chunk_bit_size`
+/// bits, i.e. `radix <= 2^chunk_bit_size`. Unlike `decompose`, `radix` need not be a power of 2. Returns
+/// big-endian representation of `chunks_count_for_radix` chunks.
+pub fn decompose_with_radix<F: PrimeField>(
+    message: &F,
+    chunk_bit_size: u8,
+    radix: CHUNK_TYPE,
+) -> crate::Result<Vec<CHUNK_TYPE>> {
+    validate_radix(chunk_bit_size, radix)?;
+    let chunks = chunks_count_for_radix::<F>(radix)?;
+    let mut m = message.into_bigint();
+    let mut decomposition = (0..chunks)
+        .map(|_| div_rem_by_small(&mut m, radix as u64) as CHUNK_TYPE)
+        .collect::<Vec<_>>();
+    decomposition.reverse();
     Ok(decomposition)
 }
 
+/// Check that `radix` is at least 2 and its chunks fit in `chunk_bit_size` bits
+pub(crate) fn validate_radix(chunk_bit_size: u8, radix: CHUNK_TYPE) -> crate::Result<()> {
+    if radix < 2 || chunk_bit_size > 16 || (radix as u32) > (1 << chunk_bit_size) {
+        return Err(SaverError::InvalidRadix(radix, chunk_bit_size));
+    }
+    Ok(())
+}
+
+/// Divide `n` in place by `d` and return the remainder
+fn div_rem_by_small<B: BigInteger>(n: &mut B, d: u64) -> u64 {
+    let d = d as u128;
+    let mut rem = 0u128;
+    for limb in n.as_mut().iter_mut().rev() {
+        let cur = (rem << 64) | (*limb as u128);
+        *limb = (cur / d) as u64;
+        rem = cur % d;
+    }
+    rem as u64
+}
+
 /// Recreate a field element back from output of `decompose`. Assumes big-endian representation in `decomposed`
 pub fn compose<F: PrimeField>(decomposed: &[CHUNK_TYPE], chunk_bit_size: u8) -> crate::Result<F> {
     match chunk_bit_size {
         4 => {
-            if (decomposed.len() % 2) == 1 {
-                return Err(SaverError::InvalidDecomposition);
+            let mut bytes = Vec::<u8>::with_capacity(decomposed.len().div_ceil(2));
+            // An odd number of nibbles means the most significant byte has only the lower nibble
+            let (first, rest) = decomposed.split_at(decomposed.len() % 2);
+            if let Some(n) = first.first() {
+                bytes.push(*n as u8);
             }
-            let mut bytes = Vec::<u8>::with_capacity(decomposed.len() / 2);
-            for nibbles in decomposed.chunks(2) {
+            for nibbles in rest.chunks(2) {
                 bytes.push(((nibbles[0] << 4) + nibbles[1]) as u8);
             }
             Ok(F::from_be_bytes_mod_order(&bytes))
@@ -72,6 +209,131 @@ pub fn compose<F: PrimeField>(decomposed: &[CHUNK_TYPE], chunk_bit_size: u8) ->
     }
 }
 
+/// Recreate a field element back from output of `decompose_with_radix`, i.e. compute
+/// `sum(decomposed[i] * radix^{n-1-i})` where `n` is the number of chunks. Assumes big-endian representation in
+/// `decomposed` and returns an error if any chunk is not less than `radix`.
+pub fn compose_with_radix<F: PrimeField>(
+    decomposed: &[CHUNK_TYPE],
+    radix: CHUNK_TYPE,
+) -> crate::Result<F> {
+    if radix < 2 {
+        return Err(SaverError::InvalidRadix(radix, 0));
+    }
+    if decomposed.iter().any(|c| *c >= radix) {
+        return Err(SaverError::InvalidDecomposition);
+    }
+    let radix = F::from(radix as u64);
+    Ok(decomposed
+        .iter()
+        .fold(F::zero(), |acc, c| acc * radix + F::from(*c as u64)))
+}
+
+/// Powers of a radix in big-endian order, i.e. `radix^{chunks-1}, ..., radix, 1`, which are the factors by which the
+/// chunks of a decomposition are multiplied to get the message. Compute them once for a given radix and number of
+/// chunks and reuse them for several commitments like with `ChunkedCommitment::new_with_cache`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadixPowers<F: PrimeField> {
+    radix: CHUNK_TYPE,
+    powers: Vec<F>,
+}
+
+impl<F: PrimeField> RadixPowers<F> {
+    /// Returns an error if `radix` is less than 2
+    pub fn new(radix: CHUNK_TYPE, chunks: u8) -> crate::Result<Self> {
+        if radix < 2 {
+            return Err(SaverError::InvalidRadix(radix, 0));
+        }
+        Ok(Self {
+            radix,
+            powers: radix_powers(radix as u64, chunks),
+        })
+    }
+
+    /// Same as `Self::new` with `chunks_count_for_radix` chunks, i.e. as many chunks as `decompose_with_radix` gives
+    pub fn for_field(radix: CHUNK_TYPE) -> crate::Result<Self> {
+        Self::new(radix, chunks_count_for_radix::<F>(radix)?)
+    }
+
+    pub fn radix(&self) -> CHUNK_TYPE {
+        self.radix
+    }
+
+    pub fn powers(&self) -> &[F] {
+        &self.powers
+    }
+
+    /// Number of chunks these powers are for
+    pub fn len(&self) -> usize {
+        self.powers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.powers.is_empty()
+    }
+}
+
+/// Return `radix^{chunks-1}, ..., radix, 1`. Takes a `u64` radix so that it also works for `2^16`
+pub(crate) fn radix_powers<F: PrimeField>(radix: u64, chunks: u8) -> Vec<F> {
+    let radix = F::from(radix);
+    let mut powers = Vec::with_capacity(chunks as usize);
+    let mut curr = F::one();
+    for _ in 0..chunks {
+        powers.push(curr);
+        curr *= radix;
+    }
+    powers.reverse();
+    powers
+}
+
+/// Number of bytes packed in one field element by `bytes_to_field_elements`. This is the largest number of bytes
+/// whose every value is less than the modulus of `F`.
+pub fn bytes_per_field_element<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize - 1) / 8
+}
+
+/// Number of field elements that `bytes_to_field_elements` creates from `byte_count` bytes
+pub fn field_elements_count_for_bytes<F: PrimeField>(byte_count: usize) -> usize {
+    byte_count / bytes_per_field_element::<F>() + 1
+}
+
+/// Pack the byte string `data` in field elements. Byte `0x80` is appended to `data` followed by as many 0 bytes
+/// as needed to make its length a multiple of `bytes_per_field_element`. Each group of `bytes_per_field_element`
+/// bytes is then read as a big-endian integer, which is always less than the modulus. The padding is always
+/// present so byte strings that differ only in trailing zeros give different field elements.
+pub fn bytes_to_field_elements<F: PrimeField>(data: &[u8]) -> Vec<F> {
+    let n = bytes_per_field_element::<F>();
+    let mut padded = Vec::with_capacity(field_elements_count_for_bytes::<F>(data.len()) * n);
+    padded.extend_from_slice(data);
+    padded.push(0x80);
+    padded.resize(padded.len().div_ceil(n) * n, 0);
+    padded
+        .chunks(n)
+        .map(|c| F::from_be_bytes_mod_order(c))
+        .collect()
+}
+
+/// Recreate the byte string from the output of `bytes_to_field_elements`. Returns an error if an element doesn't
+/// fit in `bytes_per_field_element` bytes or the padding is incorrect.
+pub fn field_elements_to_bytes<F: PrimeField>(elems: &[F]) -> crate::Result<Vec<u8>> {
+    let n = bytes_per_field_element::<F>();
+    let mut bytes = Vec::with_capacity(elems.len() * n);
+    for e in elems {
+        let b = e.into_bigint().to_bytes_be();
+        let (high, low) = b.split_at(b.len() - n);
+        if high.iter().any(|b| *b != 0) {
+            return Err(SaverError::InvalidBytePadding);
+        }
+        bytes.extend_from_slice(low);
+    }
+    while let Some(0) = bytes.last() {
+        bytes.pop();
+    }
+    match bytes.pop() {
+        Some(0x80) if bytes.len() + n >= elems.len() * n => Ok(bytes),
+        _ => Err(SaverError::InvalidBytePadding),
+    }
+}
+
 #[cfg(test)]
 #[macro_export]
 macro_rules! test_serialization {
@@ -105,11 +367,67 @@ pub(crate) mod tests {
     use ark_ec::pairing::Pairing;
     use ark_std::{
         rand::{prelude::StdRng, SeedableRng},
-        UniformRand,
+        UniformRand, Zero,
     };
 
     type Fr = <Bls12_381 as Pairing>::ScalarField;
 
+    #[test]
+    fn bytes_to_field_elements_and_back() {
+        let n = bytes_per_field_element::<Fr>();
+        assert_eq!(n, 31);
+        for len in [0, 1, n - 1, n, n + 1, 3 * n, 100] {
+            let data = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let elems = bytes_to_field_elements::<Fr>(&data);
+            assert_eq!(elems.len(), field_elements_count_for_bytes::<Fr>(len));
+            assert_eq!(field_elements_to_bytes(&elems).unwrap(), data);
+        }
+
+        // Trailing zeros are not lost
+        let elems = bytes_to_field_elements::<Fr>(&[1, 0, 0]);
+        assert_ne!(elems, bytes_to_field_elements::<Fr>(&[1]));
+        assert_eq!(field_elements_to_bytes(&elems).unwrap(), vec![1, 0, 0]);
+
+        // Missing padding, padding not in the last element and an element larger than the packed bytes
+        assert!(field_elements_to_bytes(&[Fr::from(1u64)]).is_err());
+        assert!(field_elements_to_bytes(&[Fr::zero()]).is_err());
+        let mut elems = bytes_to_field_elements::<Fr>(&[1, 2, 3]);
+        elems.push(Fr::zero());
+        assert!(field_elements_to_bytes(&elems).is_err());
+        assert!(field_elements_to_bytes(&[-Fr::from(1u64)]).is_err());
+    }
+
+    #[test]
+    fn radix_powers() {
+        let powers = RadixPowers::<Fr>::new(10, 4).unwrap();
+        assert_eq!(powers.radix(), 10);
+        assert_eq!(
+            powers.powers(),
+            &[
+                Fr::from(1000u64),
+                Fr::from(100u64),
+                Fr::from(10u64),
+                Fr::from(1u64)
+            ]
+        );
+        assert!(RadixPowers::<Fr>::new(1, 4).is_err());
+
+        for radix in [3, 10, 16, 1000] {
+            let powers = RadixPowers::<Fr>::for_field(radix).unwrap();
+            assert_eq!(
+                powers.len(),
+                chunks_count_for_radix::<Fr>(radix).unwrap() as usize
+            );
+            let decomposed = decompose_with_radix(&Fr::from(123456789u64), 16, radix).unwrap();
+            let composed = decomposed
+                .iter()
+                .zip(powers.powers())
+                .map(|(c, p)| Fr::from(*c as u64) * p)
+                .sum::<Fr>();
+            assert_eq!(composed, Fr::from(123456789u64));
+        }
+    }
+
     #[test]
     fn compose_decompose() {
         let n1 = Fr::from(53u64);
@@ -209,4 +527,148 @@ pub(crate) mod tests {
             }
         }
     }
+    #[test]
+    fn decompose_to_chunks_count() {
+        let m = Fr::from(0x1234u64);
+        assert_eq!(decompose_to(&m, 4, 4).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(decompose_to(&m, 4, 6).unwrap(), vec![0, 0, 1, 2, 3, 4]);
+        assert_eq!(decompose_to(&m, 8, 2).unwrap(), vec![0x12, 0x34]);
+        assert_eq!(decompose_to(&m, 16, 1).unwrap(), vec![0x1234]);
+        assert!(matches!(
+            decompose_to(&m, 4, 3),
+            Err(SaverError::MessageTooLargeForChunks(3))
+        ));
+        assert!(matches!(
+            decompose_to(&m, 8, 1),
+            Err(SaverError::MessageTooLargeForChunks(1))
+        ));
+        assert!(decompose_to(&Fr::zero(), 8, 0).unwrap().is_empty());
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for chunk_bit_size in [4, 8, 16] {
+            let m = Fr::rand(&mut rng);
+            let count = chunks_count::<Fr>(chunk_bit_size);
+            assert_eq!(
+                decompose_to(&m, chunk_bit_size, count).unwrap(),
+                decompose(&m, chunk_bit_size).unwrap()
+            );
+            let padded = decompose_to(&m, chunk_bit_size, count + 2).unwrap();
+            assert_eq!(compose::<Fr>(&padded, chunk_bit_size).unwrap(), m);
+        }
+    }
+
+    #[test]
+    fn decompose_validation() {
+        let n = Fr::from(7986u64);
+        for b in [0, 1, 5, 9, 32] {
+            assert!(matches!(
+                decompose(&n, b),
+                Err(SaverError::UnexpectedBase(c)) if c == b
+            ));
+            assert!(matches!(
+                try_chunks_count::<Fr>(b),
+                Err(SaverError::UnexpectedBase(c)) if c == b
+            ));
+            assert!(matches!(
+                decompose_to(&n, b, 10),
+                Err(SaverError::UnexpectedBase(c)) if c == b
+            ));
+        }
+        for b in [4, 8, 16] {
+            assert_eq!(
+                decompose(&n, b).unwrap().len(),
+                chunks_count::<Fr>(b) as usize
+            );
+            assert_eq!(try_chunks_count::<Fr>(b).unwrap(), chunks_count::<Fr>(b));
+        }
+
+        // The 377-bit modulus of this field takes 48 bytes, so the byte representation has one more 4-bit
+        // chunk than needed
+        type Fq377 = ark_bls12_377::Fq;
+        let mut rng = StdRng::seed_from_u64(0u64);
+        assert_eq!(chunks_count::<Fq377>(4), 95);
+        for _ in 0..10 {
+            let n = Fq377::rand(&mut rng);
+            for b in [4, 8, 16] {
+                let decomposed = decompose(&n, b).unwrap();
+                assert_eq!(decomposed.len(), chunks_count::<Fq377>(b) as usize);
+                assert_eq!(n, compose(&decomposed, b).unwrap());
+            }
+        }
+    }
+
+    #[cfg(feature = "constant-time")]
+    #[test]
+    fn constant_time_decompose() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for _ in 0..1000 {
+            let n = Fr::rand(&mut rng);
+            for b in [4, 8, 16] {
+                assert_eq!(decompose_ct(&n, b).unwrap(), decompose(&n, b).unwrap());
+            }
+        }
+        for b in [4, 8, 16] {
+            assert_eq!(
+                decompose_ct(&Fr::from(0u64), b).unwrap(),
+                decompose(&Fr::from(0u64), b).unwrap()
+            );
+            assert_eq!(
+                decompose_ct(&-Fr::from(1u64), b).unwrap(),
+                decompose(&-Fr::from(1u64), b).unwrap()
+            );
+        }
+        assert!(decompose_ct(&Fr::from(1u64), 5).is_err());
+    }
+
+    #[test]
+    fn decompose_with_non_power_of_2_radix() {
+        assert_eq!(
+            chunks_count_for_radix::<Fr>(16).unwrap(),
+            chunks_count::<Fr>(4)
+        );
+        assert_eq!(
+            chunks_count_for_radix::<Fr>(256).unwrap(),
+            chunks_count::<Fr>(8)
+        );
+        // 10^76 < p < 10^77
+        assert_eq!(chunks_count_for_radix::<Fr>(10).unwrap(), 77);
+
+        let n = Fr::from(7986u64);
+        let decomposed = decompose_with_radix(&n, 4, 10).unwrap();
+        assert_eq!(decomposed.len(), 77);
+        for i in decomposed[..73].iter() {
+            assert_eq!(*i, 0);
+        }
+        assert_eq!(decomposed[73..], vec![7, 9, 8, 6][..]);
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for _ in 0..100 {
+            let n = Fr::rand(&mut rng);
+            // For a power of 2 radix, the decomposition is same as `decompose`
+            assert_eq!(
+                decompose_with_radix(&n, 4, 16).unwrap(),
+                decompose(&n, 4).unwrap()
+            );
+            assert_eq!(
+                decompose_with_radix(&n, 8, 256).unwrap(),
+                decompose(&n, 8).unwrap()
+            );
+        }
+
+        assert!(decompose_with_radix(&n, 4, 17).is_err());
+        assert!(decompose_with_radix(&n, 4, 1).is_err());
+    }
+
+    #[test]
+    fn compose_decompose_with_radix() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for _ in 0..100 {
+            let n = Fr::rand(&mut rng);
+            for (chunk_bit_size, radix) in [(4, 10), (4, 16), (8, 200), (8, 256), (16, 1000)] {
+                let decomposed = decompose_with_radix(&n, chunk_bit_size, radix).unwrap();
+                assert_eq!(n, compose_with_radix(&decomposed, radix).unwrap());
+            }
+        }
+        assert!(compose_with_radix::<Fr>(&[1, 10, 2], 10).is_err());
+    }
 }
@@ -15,12 +15,13 @@
 //! 3. Computes 2 responses `s1 = r1 + c*x1` and `s2 = r2 + c*x2` and sends them to the verifier.
 //! 4. Verifier checks if `g1 * s1 + g2 * s2 = t + y*c`
 
-use crate::error::SchnorrError;
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::PrimeField;
+use crate::{compute_random_oracle_challenge, error::SchnorrError};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{io::Write, vec::Vec};
-use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use ark_std::{io::Write, rand::RngCore, vec::Vec, UniformRand};
+use digest::Digest;
+use dock_crypto_utils::{msm::WindowTable, serde_utils::ArkObjectBytes};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -143,7 +144,10 @@ impl<G: AffineRepr> PokDiscreteLogProtocol<G> {
         Self::compute_challenge_contribution(base, y, &self.t, writer)
     }
 
+    /// The challenge must not be zero as then the proof is accepted for any `t`. This is only checked in debug
+    /// builds, use `Self::try_gen_proof` to have it checked always.
     pub fn gen_proof(self, challenge: &G::ScalarField) -> PokDiscreteLog<G> {
+        debug_assert!(!challenge.is_zero(), "challenge must not be zero");
         let response = self.blinding + (self.witness * *challenge);
         PokDiscreteLog {
             t: self.t,
@@ -151,6 +155,17 @@ impl<G: AffineRepr> PokDiscreteLogProtocol<G> {
         }
     }
 
+    /// Same as `Self::gen_proof` but returns an error for a zero challenge
+    pub fn try_gen_proof(
+        self,
+        challenge: &G::ScalarField,
+    ) -> Result<PokDiscreteLog<G>, SchnorrError> {
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
+        Ok(self.gen_proof(challenge))
+    }
+
     pub fn compute_challenge_contribution<W: Write>(
         base: &G,
         y: &G,
@@ -161,6 +176,22 @@ impl<G: AffineRepr> PokDiscreteLogProtocol<G> {
         y.serialize_compressed(&mut writer)?;
         t.serialize_compressed(writer).map_err(|e| e.into())
     }
+
+    /// Create proofs for several independent protocols where each proof gets its own challenge as the hash of
+    /// `context || t` using `compute_random_oracle_challenge`. `context` must contain everything the challenge should
+    /// bind to that isn't in `t`, like the bases and the `y`s. Verify with `PokDiscreteLog::verify_batch_fiat_shamir`.
+    pub fn gen_proofs_batch<D: Digest>(
+        protocols: Vec<Self>,
+        context: &[u8],
+    ) -> Vec<PokDiscreteLog<G>> {
+        protocols
+            .into_iter()
+            .map(|p| {
+                let challenge = PokDiscreteLog::fiat_shamir_challenge::<D>(context, &p.t);
+                p.gen_proof(&challenge)
+            })
+            .collect()
+    }
 }
 
 impl<G: AffineRepr> PokDiscreteLog<G> {
@@ -173,11 +204,113 @@ impl<G: AffineRepr> PokDiscreteLog<G> {
         PokDiscreteLogProtocol::compute_challenge_contribution(base, y, &self.t, writer)
     }
 
-    /// `base*response - y*challenge == t`
+    /// `base*response - y*challenge == t`. Returns false for a zero challenge as then any `t` is accepted.
     pub fn verify(&self, y: &G, base: &G, challenge: &G::ScalarField) -> bool {
+        self.is_valid(y, base, challenge).is_ok()
+    }
+
+    /// Same as `Self::verify` but returns `SchnorrError::ZeroChallenge` for a zero challenge and
+    /// `SchnorrError::InvalidResponse` if the proof is invalid.
+    pub fn is_valid(
+        &self,
+        y: &G,
+        base: &G,
+        challenge: &G::ScalarField,
+    ) -> Result<(), SchnorrError> {
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
         let mut expected = base.mul_bigint(self.response.into_bigint());
         expected -= y.mul_bigint(challenge.into_bigint());
-        expected.into_affine() == self.t
+        if expected.into_affine() == self.t {
+            Ok(())
+        } else {
+            Err(SchnorrError::InvalidResponse)
+        }
+    }
+
+    /// Same as `Self::verify` but first checks that `y` and `t` are in the prime order subgroup. Without this, on
+    /// curves with a cofactor like the G1 and G2 of BLS12-381 and the G2 of BN254, a prover can add a point of small
+    /// order to `y` and a multiple of it to `t` and still pass `Self::verify`. Points deserialized with validation,
+    /// like with `deserialize_compressed`, are already checked to be in the subgroup so this is only needed when `y`
+    /// or `t` were created or deserialized without that check. Curves of prime order like BN254's G1 don't need it.
+    pub fn verify_in_subgroup(&self, y: &G, base: &G, challenge: &G::ScalarField) -> bool {
+        y.mul_bigint(G::ScalarField::MODULUS).is_zero()
+            && self.t.mul_bigint(G::ScalarField::MODULUS).is_zero()
+            && self.verify(y, base, challenge)
+    }
+
+    /// Same as `Self::verify` but `base * response` is computed using the window table of `base` created by
+    /// `Self::precompute_base` and the comparison with `t` is done in projective coordinates. Useful when many proofs
+    /// are verified against the same `base`.
+    pub fn verify_with_table(
+        &self,
+        y: &G,
+        base_table: &WindowTable<G::Group>,
+        challenge: &G::ScalarField,
+    ) -> bool {
+        if challenge.is_zero() {
+            return false;
+        }
+        let mut expected = base_table.multiply(&self.response);
+        expected -= y.mul_bigint(challenge.into_bigint());
+        expected == self.t.into_group()
+    }
+
+    /// Create the window table of `base` for `Self::verify_with_table`. `num_proofs` is the approximate number of
+    /// proofs that will be verified using it.
+    pub fn precompute_base(base: &G, num_proofs: usize) -> WindowTable<G::Group> {
+        WindowTable::new(num_proofs, base.into_group())
+    }
+
+    /// Verify proofs created by `PokDiscreteLogProtocol::gen_proofs_batch` with the same `context`, each given with
+    /// its `y`, that share the same `base`. Returns false if any proof is invalid.
+    pub fn verify_batch_fiat_shamir<D: Digest>(
+        proofs: &[(&Self, &G)],
+        base: &G,
+        context: &[u8],
+    ) -> bool {
+        proofs.iter().all(|(proof, y)| {
+            proof.verify(
+                y,
+                base,
+                &Self::fiat_shamir_challenge::<D>(context, &proof.t),
+            )
+        })
+    }
+
+    fn fiat_shamir_challenge<D: Digest>(context: &[u8], t: &G) -> G::ScalarField {
+        let mut bytes = context.to_vec();
+        t.serialize_compressed(&mut bytes)
+            .expect("serialization failed");
+        compute_random_oracle_challenge::<G::ScalarField, D>(&bytes)
+    }
+
+    /// Verify several proofs of knowledge of discrete log, each given with its `y` and challenge, that share the
+    /// same `base`. Checks `base*(sum r_i*response_i) - sum r_i*t_i - sum (r_i*challenge_i)*y_i == 0` with one
+    /// MSM where `r_i` are random. Returns false if any challenge is zero.
+    pub fn verify_batch<R: RngCore>(
+        rng: &mut R,
+        proofs: &[(&Self, &G, &G::ScalarField)],
+        base: &G,
+    ) -> bool {
+        let mut bases = Vec::with_capacity(2 * proofs.len() + 1);
+        let mut scalars = Vec::with_capacity(2 * proofs.len() + 1);
+        let mut response_sum = G::ScalarField::zero();
+        for (proof, y, challenge) in proofs {
+            if challenge.is_zero() {
+                return false;
+            }
+            let r = G::ScalarField::rand(rng);
+            response_sum += r * proof.response;
+            bases.push(proof.t);
+            scalars.push(-r);
+            bases.push(**y);
+            scalars.push(-(r * *challenge));
+        }
+        bases.push(*base);
+        scalars.push(response_sum);
+        G::Group::msm_unchecked(&bases, &scalars).is_zero()
     }
 }
 
@@ -212,7 +345,10 @@ impl<G: AffineRepr> PokTwoDiscreteLogsProtocol<G> {
         Self::compute_challenge_contribution(base1, base2, y, &self.t, writer)
     }
 
+    /// The challenge must not be zero as then the proof is accepted for any `t`. This is only checked in debug
+    /// builds, use `Self::try_gen_proof` to have it checked always.
     pub fn gen_proof(self, challenge: &G::ScalarField) -> PokTwoDiscreteLogs<G> {
+        debug_assert!(!challenge.is_zero(), "challenge must not be zero");
         let response1 = self.blinding1 + (self.witness1 * *challenge);
         let response2 = self.blinding2 + (self.witness2 * *challenge);
         PokTwoDiscreteLogs {
@@ -222,6 +358,17 @@ impl<G: AffineRepr> PokTwoDiscreteLogsProtocol<G> {
         }
     }
 
+    /// Same as `Self::gen_proof` but returns an error for a zero challenge
+    pub fn try_gen_proof(
+        self,
+        challenge: &G::ScalarField,
+    ) -> Result<PokTwoDiscreteLogs<G>, SchnorrError> {
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
+        Ok(self.gen_proof(challenge))
+    }
+
     pub fn compute_challenge_contribution<W: Write>(
         base1: &G,
         base2: &G,
@@ -248,19 +395,38 @@ impl<G: AffineRepr> PokTwoDiscreteLogs<G> {
         PokTwoDiscreteLogsProtocol::compute_challenge_contribution(base1, base2, y, &self.t, writer)
     }
 
-    /// `base1*response1 + base2*response2 - y*challenge == t`
+    /// `base1*response1 + base2*response2 - y*challenge == t`. Returns false for a zero challenge.
     pub fn verify(&self, y: &G, base1: &G, base2: &G, challenge: &G::ScalarField) -> bool {
+        self.is_valid(y, base1, base2, challenge).is_ok()
+    }
+
+    /// Same as `Self::verify` but returns `SchnorrError::ZeroChallenge` for a zero challenge and
+    /// `SchnorrError::InvalidResponse` if the proof is invalid.
+    pub fn is_valid(
+        &self,
+        y: &G,
+        base1: &G,
+        base2: &G,
+        challenge: &G::ScalarField,
+    ) -> Result<(), SchnorrError> {
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
         let mut expected = base1.mul_bigint(self.response1.into_bigint());
         expected += base2.mul_bigint(self.response2.into_bigint());
         expected -= y.mul_bigint(challenge.into_bigint());
-        expected.into_affine() == self.t
+        if expected.into_affine() == self.t {
+            Ok(())
+        } else {
+            Err(SchnorrError::InvalidResponse)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{compute_random_oracle_challenge, test_serialization};
+    use crate::{compute_random_oracle_challenge, test_serialization, ChallengeContribution};
     use ark_bls12_381::{Bls12_381, Fr};
     use ark_ec::pairing::Pairing;
     use ark_std::{
@@ -308,6 +474,23 @@ mod tests {
                 assert_eq!(chal_contrib_prover, chal_contrib_verifier);
                 assert_eq!(challenge_prover, challenge_verifier);
 
+                let contribution_prover = ChallengeContribution(chal_contrib_prover);
+                let contribution_verifier = ChallengeContribution(chal_contrib_verifier);
+                contribution_prover.matches(&contribution_verifier).unwrap();
+                assert_eq!(
+                    contribution_verifier.challenge::<Fr, Blake2b512>(),
+                    challenge_verifier
+                );
+                // Verifier uses a different `y`
+                let mut chal_contrib_other = vec![];
+                proof
+                    .challenge_contribution(&base, &base, &mut chal_contrib_other)
+                    .unwrap();
+                assert!(matches!(
+                    contribution_prover.matches(&ChallengeContribution(chal_contrib_other)),
+                    Err(SchnorrError::ChallengeContributionMismatch)
+                ));
+
                 test_serialization!(PokDiscreteLog<<Bls12_381 as Pairing>::$group_affine>, proof);
             };
         }
@@ -316,6 +499,184 @@ mod tests {
         check!(G2Affine, G2);
     }
 
+    #[test]
+    fn zero_challenge() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let y = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+
+        // Without knowing the discrete log of `y`, anyone can create a proof that satisfies the verification
+        // equation for a zero challenge
+        let response = Fr::rand(&mut rng);
+        let proof = PokDiscreteLog {
+            t: base.mul_bigint(response.into_bigint()).into_affine(),
+            response,
+        };
+        assert!(!proof.verify(&y, &base, &Fr::zero()));
+        assert!(matches!(
+            proof.is_valid(&y, &base, &Fr::zero()),
+            Err(SchnorrError::ZeroChallenge)
+        ));
+        assert!(matches!(
+            proof.is_valid(&y, &base, &Fr::rand(&mut rng)),
+            Err(SchnorrError::InvalidResponse)
+        ));
+
+        let witness = Fr::rand(&mut rng);
+        let y = base.mul_bigint(witness.into_bigint()).into_affine();
+        let protocol = PokDiscreteLogProtocol::init(witness, Fr::rand(&mut rng), &base);
+        assert!(matches!(
+            protocol.clone().try_gen_proof(&Fr::zero()),
+            Err(SchnorrError::ZeroChallenge)
+        ));
+        let challenge = Fr::rand(&mut rng);
+        let proof = protocol.try_gen_proof(&challenge).unwrap();
+        proof.is_valid(&y, &base, &challenge).unwrap();
+    }
+
+    #[test]
+    fn schnorr_single_in_subgroup() {
+        use ark_bls12_381::{Fq, G1Affine};
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let witness = Fr::rand(&mut rng);
+        let y = base.mul_bigint(witness.into_bigint()).into_affine();
+        let challenge = Fr::rand(&mut rng);
+        let proof =
+            PokDiscreteLogProtocol::init(witness, Fr::rand(&mut rng), &base).gen_proof(&challenge);
+        assert!(proof.verify_in_subgroup(&y, &base, &challenge));
+        assert!(!proof.verify_in_subgroup(&y, &base, &Fr::rand(&mut rng)));
+
+        // A point on the curve but outside the subgroup, multiplied by the subgroup order to get a point whose
+        // order divides the cofactor
+        let mut x = Fq::rand(&mut rng);
+        let point = loop {
+            if let Some(p) = G1Affine::get_point_from_x_unchecked(x, true) {
+                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                    break p;
+                }
+            }
+            x += Fq::from(1u64);
+        };
+        let small_order = point.mul_bigint(Fr::MODULUS).into_affine();
+        assert!(!small_order.is_zero());
+
+        let bad_y = (y + small_order).into_affine();
+        let bad_proof = PokDiscreteLog {
+            t: (proof.t.into_group() - small_order * challenge).into_affine(),
+            response: proof.response,
+        };
+        assert!(bad_proof.verify(&bad_y, &base, &challenge));
+        assert!(!bad_proof.verify_in_subgroup(&bad_y, &base, &challenge));
+    }
+
+    #[test]
+    fn schnorr_single_batch() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let count = 10;
+        let mut ys = vec![];
+        let mut challenges = vec![];
+        let mut proofs = vec![];
+        for _ in 0..count {
+            let witness = Fr::rand(&mut rng);
+            ys.push(base.mul_bigint(witness.into_bigint()).into_affine());
+            let protocol = PokDiscreteLogProtocol::init(witness, Fr::rand(&mut rng), &base);
+            let challenge = Fr::rand(&mut rng);
+            proofs.push(protocol.gen_proof(&challenge));
+            challenges.push(challenge);
+        }
+
+        let mut batch = (0..count)
+            .map(|i| (&proofs[i], &ys[i], &challenges[i]))
+            .collect::<Vec<_>>();
+        assert!(PokDiscreteLog::verify_batch(&mut rng, &batch, &base));
+        assert!(PokDiscreteLog::verify_batch(&mut rng, &batch[..1], &base));
+
+        // One proof with a wrong challenge
+        let wrong_challenge = Fr::rand(&mut rng);
+        batch[3].2 = &wrong_challenge;
+        assert!(!proofs[3].verify(&ys[3], &base, &wrong_challenge));
+        assert!(!PokDiscreteLog::verify_batch(&mut rng, &batch, &base));
+
+        // One proof for a different base
+        batch[3].2 = &challenges[3];
+        let other_base = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        assert!(!PokDiscreteLog::verify_batch(&mut rng, &batch, &other_base));
+
+        // One zero challenge
+        let zero = Fr::zero();
+        batch[5].2 = &zero;
+        assert!(!PokDiscreteLog::verify_batch(&mut rng, &batch, &base));
+    }
+
+    #[test]
+    fn schnorr_single_with_table() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let count = 10;
+        let table = PokDiscreteLog::precompute_base(&base, count);
+        for _ in 0..count {
+            let witness = Fr::rand(&mut rng);
+            let y = base.mul_bigint(witness.into_bigint()).into_affine();
+            let protocol = PokDiscreteLogProtocol::init(witness, Fr::rand(&mut rng), &base);
+            let challenge = Fr::rand(&mut rng);
+            let proof = protocol.gen_proof(&challenge);
+            assert!(proof.verify(&y, &base, &challenge));
+            assert!(proof.verify_with_table(&y, &table, &challenge));
+            assert!(!proof.verify_with_table(&y, &table, &Fr::rand(&mut rng)));
+            assert!(!proof.verify_with_table(&y, &table, &Fr::zero()));
+        }
+        let other_table = PokDiscreteLog::precompute_base(
+            &<Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine(),
+            1,
+        );
+        let witness = Fr::rand(&mut rng);
+        let y = base.mul_bigint(witness.into_bigint()).into_affine();
+        let challenge = Fr::rand(&mut rng);
+        let proof =
+            PokDiscreteLogProtocol::init(witness, Fr::rand(&mut rng), &base).gen_proof(&challenge);
+        assert!(!proof.verify_with_table(&y, &other_table, &challenge));
+    }
+
+    #[test]
+    fn schnorr_single_batch_fiat_shamir() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let base = <Bls12_381 as Pairing>::G1::rand(&mut rng).into_affine();
+        let count = 5;
+        let witnesses = (0..count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let ys = witnesses
+            .iter()
+            .map(|w| base.mul_bigint(w.into_bigint()).into_affine())
+            .collect::<Vec<_>>();
+        let mut context = vec![];
+        base.serialize_compressed(&mut context).unwrap();
+        ys.serialize_compressed(&mut context).unwrap();
+
+        let protocols = witnesses
+            .iter()
+            .map(|w| PokDiscreteLogProtocol::init(*w, Fr::rand(&mut rng), &base))
+            .collect::<Vec<_>>();
+        let proofs = PokDiscreteLogProtocol::gen_proofs_batch::<Blake2b512>(protocols, &context);
+        assert_eq!(proofs.len(), count);
+        let mut batch = proofs.iter().zip(ys.iter()).collect::<Vec<_>>();
+        assert!(PokDiscreteLog::verify_batch_fiat_shamir::<Blake2b512>(
+            &batch, &base, &context
+        ));
+        assert!(!PokDiscreteLog::verify_batch_fiat_shamir::<Blake2b512>(
+            &batch,
+            &base,
+            b"other context"
+        ));
+
+        // Proofs swapped between statements
+        batch[0].1 = &ys[1];
+        assert!(!PokDiscreteLog::verify_batch_fiat_shamir::<Blake2b512>(
+            &batch, &base, &context
+        ));
+    }
+
     #[test]
     fn schnorr_double() {
         let mut rng = StdRng::seed_from_u64(0u64);
@@ -17,7 +17,7 @@ use ark_ec::{
     CurveGroup,
 };
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{io::Write, vec::Vec};
+use ark_std::{io::Write, vec::Vec, Zero};
 use dock_crypto_utils::{
     pair_g1_g2, pair_g2_g1, randomized_pairing_check::RandomizedPairingChecker,
     serde_utils::ArkObjectBytes,
@@ -109,15 +109,33 @@ macro_rules! impl_protocol {
             }
 
             impl<E: Pairing> $proof<E> {
+                /// Returns false for a zero challenge as then any `t` is accepted
                 pub fn verify(
                     &self,
                     y: &PairingOutput<E>,
                     other: impl Into<$other_group_prepared>,
                     challenge: &E::ScalarField,
                 ) -> bool {
+                    if challenge.is_zero() {
+                        return false;
+                    }
                     $pairing!(E::pairing, other, self.response) == (self.t + *y * challenge)
                 }
 
+                /// Same as `Self::verify` but also returns false if `t` is the identity. An honest prover creates such
+                /// a `t` only with negligible probability. With a `t` of identity the response is `challenge * witness`
+                /// which reveals the witness, and when `y` is the identity as well the zero response passes
+                /// `Self::verify` without proving anything. So use this when `y` can come from the prover or the
+                /// proof must not be a vacuous one.
+                pub fn verify_nontrivial(
+                    &self,
+                    y: &PairingOutput<E>,
+                    other: impl Into<$other_group_prepared>,
+                    challenge: &E::ScalarField,
+                ) -> bool {
+                    !self.t.is_zero() && self.verify(y, other, challenge)
+                }
+
                 pub fn challenge_contribution<W: Write>(
                     &self,
                     other: &$other_group,
@@ -215,6 +233,16 @@ mod tests {
                 let base_prepared = <Bls12_381 as Pairing>::$other_group_prepared::from(base);
                 assert!(proof.verify(&y, base_prepared, &challenge_verifier));
 
+                assert!(proof.verify_nontrivial(&y, &base, &challenge_verifier));
+                // A zero `t` and response for a zero `y` is accepted by `verify` but not by `verify_nontrivial`
+                let trivial = $proof::<Bls12_381> {
+                    t: PairingOutput::zero(),
+                    response: $witness_group::identity(),
+                };
+                let zero_y = PairingOutput::<Bls12_381>::zero();
+                assert!(trivial.verify(&zero_y, &base, &challenge_verifier));
+                assert!(!trivial.verify_nontrivial(&zero_y, &base, &challenge_verifier));
+
                 // Check with randomized pairing checker
                 let count = 3;
                 let bases = (0..count)
@@ -17,6 +17,16 @@ pub enum SchnorrError {
     MissingBlindingAtIndex(usize),
     MissingResponseAtIndex(usize),
     FoundCommonIndexInOwnAndReceivedResponses(usize),
+    /// The challenge is 0 which makes any commitment `t` a valid one for the response
+    ZeroChallenge,
+    /// The base at this index is the identity so its witness is unconstrained
+    InvalidBase(usize),
+    /// The bases at these indices are equal so only the sum of their witnesses is constrained
+    DuplicateBase(usize, usize),
+    /// The response has no elements, i.e. it wasn't created from any witness
+    EmptyResponse,
+    /// The prover and the verifier hashed different bytes to create the challenge
+    ChallengeContributionMismatch,
 }
 
 impl From<SerializationError> for SchnorrError {
@@ -0,0 +1,50 @@
+//! Implementations of `arbitrary::Arbitrary` for structured fuzzing. Field elements are created by reducing
+//! arbitrary bytes modulo the field order and group elements as multiples of the group generator so that
+//! the generated objects are always valid.
+
+use crate::{SchnorrCommitment, SchnorrResponse};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_std::{vec, vec::Vec};
+
+/// Number of bytes reduced to a field element. Taking more bytes than the field size makes the result close
+/// to uniform.
+fn field_element_byte_size<F: PrimeField>() -> usize {
+    F::MODULUS_BIT_SIZE as usize / 8 + 16
+}
+
+/// Create a field element from arbitrary bytes. Missing bytes are taken as 0s.
+pub fn arbitrary_field_element<F: PrimeField>(u: &mut Unstructured<'_>) -> Result<F> {
+    let mut bytes = vec![0; field_element_byte_size::<F>()];
+    u.fill_buffer(&mut bytes)?;
+    Ok(F::from_le_bytes_mod_order(&bytes))
+}
+
+/// Create a vector of field elements whose length is bounded by the remaining bytes
+pub fn arbitrary_field_elements<F: PrimeField>(u: &mut Unstructured<'_>) -> Result<Vec<F>> {
+    let max_len = u.len() / field_element_byte_size::<F>();
+    let len = u.int_in_range(0..=max_len)?;
+    (0..len).map(|_| arbitrary_field_element(u)).collect()
+}
+
+/// Create a group element as an arbitrary multiple of the generator
+pub fn arbitrary_group_element<G: AffineRepr>(u: &mut Unstructured<'_>) -> Result<G> {
+    let s = arbitrary_field_element::<G::ScalarField>(u)?;
+    Ok((G::generator() * s).into_affine())
+}
+
+impl<'a, G: AffineRepr> Arbitrary<'a> for SchnorrResponse<G> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(arbitrary_field_elements(u)?))
+    }
+}
+
+impl<'a, G: AffineRepr> Arbitrary<'a> for SchnorrCommitment<G> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            blindings: arbitrary_field_elements(u)?,
+            t: arbitrary_group_element(u)?,
+        })
+    }
+}
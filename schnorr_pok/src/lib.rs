@@ -55,19 +55,31 @@
 //! [`discrete_log_pairing`]: crate::discrete_log_pairing
 
 use crate::error::SchnorrError;
-use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
-use ark_ff::PrimeField;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{
+    borrow::Borrow,
     cfg_iter,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     io::Write,
+    marker::PhantomData,
+    mem,
     ops::Add,
+    rand::RngCore,
+    vec,
     vec::Vec,
+    UniformRand,
 };
 use digest::Digest;
-use dock_crypto_utils::{hashing_utils::field_elem_from_try_and_incr, serde_utils::*};
+use dock_crypto_utils::{
+    aliases::FullDigest,
+    hashing_utils::{field_elem_from_try_and_incr, hash_to_field},
+    msm::smart_msm,
+    serde_utils::*,
+    transcript::Transcript,
+};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -79,13 +91,44 @@ use rayon::prelude::*;
 pub mod discrete_log;
 pub mod discrete_log_pairing;
 pub mod error;
+#[cfg(feature = "arbitrary")]
+pub mod fuzzing;
 pub mod inequality;
 pub mod partial;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+
+pub mod prelude {
+    pub use crate::{
+        compute_random_oracle_challenge, compute_random_oracle_challenge_with,
+        discrete_log::{
+            PokDiscreteLog, PokDiscreteLogProtocol, PokTwoDiscreteLogs, PokTwoDiscreteLogsProtocol,
+        },
+        error::SchnorrError,
+        partial::PartialSchnorrResponse,
+        ChallengeBuilder, ChallengeContribution, ExpandMessageXmd, HashToFieldStrategy,
+        SchnorrChallengeContributor, SchnorrCommitment, SchnorrResponse, TryAndIncrement,
+    };
+}
 
 /// Trait implemented by Schnorr-based protocols for returning their contribution to the overall challenge.
 /// i.e. overall challenge is of form Hash({m_i}), and this function returns the bytecode for m_j for some j.
 pub trait SchnorrChallengeContributor {
     fn challenge_contribution<W: Write>(&self, writer: W) -> Result<(), SchnorrError>;
+
+    /// Append the contribution returned by `challenge_contribution` to the given transcript under `label`
+    fn contribute_to_transcript<T: Transcript>(
+        &self,
+        label: &'static [u8],
+        transcript: &mut T,
+    ) -> Result<(), SchnorrError> {
+        let mut bytes = Vec::new();
+        self.challenge_contribution(&mut bytes)?;
+        transcript.append_message(label, &bytes);
+        Ok(())
+    }
 }
 
 /// Commitment to randomness during step 1 of the Schnorr protocol to prove knowledge of 1 or more discrete logs
@@ -116,10 +159,104 @@ impl<G: AffineRepr> SchnorrCommitment<G> {
     /// Create commitment as `bases[0] * blindings[0] + bases[1] * blindings[1] + ... + bases[i] * blindings[i]`
     /// for step-1 of the protocol. Extra `bases` or `blindings` are ignored.
     pub fn new(bases: &[G], blindings: Vec<G::ScalarField>) -> Self {
-        let t = G::Group::msm_unchecked(bases, &blindings).into_affine();
+        let t = smart_msm(bases, &blindings).into_affine();
         Self { blindings, t }
     }
 
+    /// Same as `Self::new` but first checks that no base is the identity and no 2 bases are equal as the proof
+    /// wouldn't constrain the witnesses of such bases individually. Returns `SchnorrError::InvalidBase` or
+    /// `SchnorrError::DuplicateBase` with the indices of the offending bases.
+    pub fn new_validated(
+        bases: &[G],
+        blindings: Vec<G::ScalarField>,
+    ) -> Result<Self, SchnorrError> {
+        let mut positions = BTreeMap::<Vec<u8>, usize>::new();
+        for (i, b) in bases.iter().enumerate() {
+            if b.is_zero() {
+                return Err(SchnorrError::InvalidBase(i));
+            }
+            let mut key = Vec::with_capacity(b.compressed_size());
+            b.serialize_compressed(&mut key)?;
+            if let Some(&j) = positions.get(&key) {
+                return Err(SchnorrError::DuplicateBase(j, i));
+            }
+            positions.insert(key, i);
+        }
+        Ok(Self::new(bases, blindings))
+    }
+
+    /// Same as `Self::new` but takes the blindings as an iterator, like one sampling from an RNG, and
+    /// allocates the stored `blindings` only once.
+    pub fn new_from_iter(
+        bases: &[G],
+        blindings: impl ExactSizeIterator<Item = G::ScalarField>,
+    ) -> Self {
+        let mut b = Vec::with_capacity(blindings.len());
+        b.extend(blindings);
+        Self::new(bases, b)
+    }
+
+    /// Same as `Self::new` but the commitment `t` is not converted to affine form. Useful when several
+    /// commitments are combined before being converted to affine together.
+    pub fn new_projective(
+        bases: &[G],
+        blindings: Vec<G::ScalarField>,
+    ) -> SchnorrCommitmentProjective<G> {
+        SchnorrCommitmentProjective::new(bases, blindings)
+    }
+
+    /// Same as `Self::new` but the blindings of repeated bases are added before the multi-scalar multiplication
+    /// so it's smaller when `bases` has many repeated elements. The commitment `t` is the same as the one
+    /// created by `Self::new` so the responses can be checked with either `SchnorrResponse::is_valid` or
+    /// `SchnorrResponse::is_valid_dedup`.
+    pub fn new_dedup(bases: &[G], blindings: Vec<G::ScalarField>) -> Self {
+        let (bases, scalars) = sum_scalars_of_repeated_bases(bases, &blindings);
+        let t = smart_msm(&bases, &scalars).into_affine();
+        Self { blindings, t }
+    }
+
+    /// Same as `Self::new` but commits only to the bases at `indices` of `bases`, i.e. creates commitment as
+    /// `bases[indices[0]] * blindings[0] + ... + bases[indices[i]] * blindings[i]`. Useful when knowledge is proven
+    /// over only a subset of a large set of bases. The responses are checked with `SchnorrResponse::is_valid_selected`.
+    pub fn new_selected(
+        bases: &[G],
+        blindings: Vec<G::ScalarField>,
+        indices: &[usize],
+    ) -> Result<Self, SchnorrError> {
+        expect_equality!(
+            indices.len(),
+            blindings.len(),
+            SchnorrError::ExpectedSameSizeSequences
+        );
+        let selected = select_bases(bases, indices)?;
+        Ok(Self::new(&selected, blindings))
+    }
+
+    /// Same as `Self::new` but the blindings are created here, 0 for each witness `i` where `disclosed[i]` is true
+    /// and random for the rest, like when some of the witnesses are revealed to the verifier. This is not constant
+    /// time. The `subtle` feature only makes the choice between 0 and random blinding not branch on `disclosed`,
+    /// but the multi-scalar multiplication in `Self::new` takes time depending on which blindings are 0, so the time
+    /// taken can still leak which witnesses are disclosed.
+    pub fn new_partial<R: RngCore>(rng: &mut R, bases: &[G], disclosed: &[bool]) -> Self {
+        let zero = G::ScalarField::zero();
+        let blindings = disclosed
+            .iter()
+            .map(|d| cond_select_scalar(*d, &zero, &G::ScalarField::rand(rng)))
+            .collect();
+        Self::new(bases, blindings)
+    }
+
+    /// Sum the commitments `t` of several parties, like in a distributed protocol where each party commits to its
+    /// share of the blindings. The sum is done in projective form and converted to affine once. The blindings are
+    /// not summed as they stay private with each party.
+    pub fn sum_commitments(comms: &[&Self]) -> G {
+        comms
+            .iter()
+            .map(|c| c.t.into_group())
+            .sum::<G::Group>()
+            .into_affine()
+    }
+
     /// Create responses for each witness (discrete log) as `response[i] = self.blindings[i] + (witnesses[i] * challenge)`
     pub fn response(
         &self,
@@ -149,6 +286,46 @@ impl<G: AffineRepr> SchnorrChallengeContributor for SchnorrCommitment<G> {
     }
 }
 
+/// Same as `SchnorrCommitment` but the commitment `t` is in projective form. Convert many of these to
+/// `SchnorrCommitment` using `Self::batch_into_affine` which needs a single field inversion.
+#[derive(Clone, Debug, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct SchnorrCommitmentProjective<G: AffineRepr> {
+    /// Randomness. 1 per discrete log
+    pub blindings: Vec<G::ScalarField>,
+    /// The commitment to all the randomnesses, i.e. `bases[0] * blindings[0] + ... + bases[i] * blindings[i]`
+    #[zeroize(skip)]
+    pub t: G::Group,
+}
+
+impl<G: AffineRepr> SchnorrCommitmentProjective<G> {
+    /// Create commitment as `bases[0] * blindings[0] + bases[1] * blindings[1] + ... + bases[i] * blindings[i]`
+    /// for step-1 of the protocol. Extra `bases` or `blindings` are ignored.
+    pub fn new(bases: &[G], blindings: Vec<G::ScalarField>) -> Self {
+        let t = smart_msm(bases, &blindings);
+        Self { blindings, t }
+    }
+
+    pub fn into_affine(mut self) -> SchnorrCommitment<G> {
+        SchnorrCommitment {
+            blindings: mem::take(&mut self.blindings),
+            t: self.t.into_affine(),
+        }
+    }
+
+    /// Convert all commitments to affine form together
+    pub fn batch_into_affine(mut commitments: Vec<Self>) -> Vec<SchnorrCommitment<G>> {
+        let ts = G::Group::normalize_batch(&commitments.iter().map(|c| c.t).collect::<Vec<_>>());
+        commitments
+            .iter_mut()
+            .zip(ts)
+            .map(|(c, t)| SchnorrCommitment {
+                blindings: mem::take(&mut c.blindings),
+                t,
+            })
+            .collect()
+    }
+}
+
 /// Response during step 3 of the Schnorr protocol to prove knowledge of 1 or more discrete logs
 #[serde_as]
 #[derive(
@@ -160,20 +337,93 @@ pub struct SchnorrResponse<G: AffineRepr>(
 
 impl<G: AffineRepr> SchnorrResponse<G> {
     /// Check if response is valid and thus validity of Schnorr proof
-    /// `bases[0]*responses[0] + bases[0]*responses[0] + ... + bases[i]*responses[i] - y*challenge == t`
+    /// `bases[0]*responses[0] + bases[0]*responses[0] + ... + bases[i]*responses[i] - y*challenge == t`.
+    /// The challenge can be passed as `&G::ScalarField` or as any type that borrows as one, like a
+    /// domain specific wrapper over the challenge.
+    /// `y*challenge` is subtracted from the multi-scalar multiplication over `bases` rather than appending `y` to
+    /// `bases`, so `bases` is used as given and not copied. Thus this doesn't allocate per verification and can be
+    /// called in a loop without a scratch buffer.
     pub fn is_valid(
         &self,
         bases: &[G],
         y: &G,
         t: &G,
+        challenge: impl Borrow<G::ScalarField>,
+    ) -> Result<(), SchnorrError> {
+        let challenge = challenge.borrow();
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
+        if self.reconstruct_commitment(bases, y, challenge)? == *t {
+            Ok(())
+        } else {
+            Err(SchnorrError::InvalidResponse)
+        }
+    }
+
+    /// Compute the commitment `t` that makes this response valid, i.e.
+    /// `bases[0]*responses[0] + ... + bases[i]*responses[i] - y*challenge`. `Self::is_valid` compares this
+    /// to the received `t` so it can be recorded, like for an audit trail of verified proofs.
+    pub fn reconstruct_commitment(
+        &self,
+        bases: &[G],
+        y: &G,
         challenge: &G::ScalarField,
+    ) -> Result<G, SchnorrError> {
+        expect_equality!(
+            self.0.len(),
+            bases.len(),
+            SchnorrError::ExpectedSameSizeSequences
+        );
+        Ok((smart_msm(bases, &self.0) - *y * challenge).into_affine())
+    }
+
+    /// Same as `Self::is_valid` but `y` and `t` are in projective form, like when they are sums of group
+    /// elements, so they don't need to be converted to affine. The comparison with `t` is done in projective
+    /// coordinates.
+    pub fn is_valid_projective(
+        &self,
+        bases: &[G],
+        y: &G::Group,
+        t: &G::Group,
+        challenge: impl Borrow<G::ScalarField>,
+    ) -> Result<(), SchnorrError> {
+        expect_equality!(
+            self.0.len(),
+            bases.len(),
+            SchnorrError::ExpectedSameSizeSequences
+        );
+        let challenge = challenge.borrow();
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
+        if smart_msm(bases, &self.0) - *y * challenge == *t {
+            Ok(())
+        } else {
+            Err(SchnorrError::InvalidResponse)
+        }
+    }
+
+    /// Same as `Self::is_valid` but the responses of repeated bases are added before the multi-scalar
+    /// multiplication. Useful when `bases` has many repeated elements.
+    pub fn is_valid_dedup(
+        &self,
+        bases: &[G],
+        y: &G,
+        t: &G,
+        challenge: impl Borrow<G::ScalarField>,
     ) -> Result<(), SchnorrError> {
         expect_equality!(
             self.0.len(),
             bases.len(),
             SchnorrError::ExpectedSameSizeSequences
         );
-        if (G::Group::msm_unchecked(bases, &self.0).add(y.mul_bigint((-*challenge).into_bigint())))
+        let challenge = challenge.borrow();
+        if challenge.is_zero() {
+            return Err(SchnorrError::ZeroChallenge);
+        }
+        let (bases, responses) = sum_scalars_of_repeated_bases(bases, &self.0);
+        if (smart_msm(&bases, &responses).add(y.mul_bigint((-*challenge).into_bigint())))
             .into_affine()
             == *t
         {
@@ -183,9 +433,41 @@ impl<G: AffineRepr> SchnorrResponse<G> {
         }
     }
 
-    /// Get response for the specified discrete log
+    /// Same as `Self::is_valid` but only the bases at `indices` of `bases` are used, in that order, as in
+    /// `SchnorrCommitment::new_selected`.
+    pub fn is_valid_selected(
+        &self,
+        bases: &[G],
+        indices: &[usize],
+        y: &G,
+        t: &G,
+        challenge: impl Borrow<G::ScalarField>,
+    ) -> Result<(), SchnorrError> {
+        let selected = select_bases(bases, indices)?;
+        self.is_valid(&selected, y, t, challenge)
+    }
+
+    /// Sum the responses of several parties created with shares of the witnesses and blindings, like in a distributed
+    /// protocol. The sum is a valid response for the sum of the commitments created by
+    /// `SchnorrCommitment::sum_commitments`. All responses must be for the same number of witnesses.
+    pub fn sum_responses(responses: &[&Self]) -> Result<Self, SchnorrError> {
+        let len = responses.first().map(|r| r.len()).unwrap_or(0);
+        let mut sum = vec![G::ScalarField::zero(); len];
+        for r in responses {
+            expect_equality!(r.len(), len, SchnorrError::ExpectedSameSizeSequences);
+            for (s, r) in sum.iter_mut().zip(r.0.iter()) {
+                *s += r;
+            }
+        }
+        Ok(Self(sum))
+    }
+
+    /// Get response for the specified discrete log. Returns `SchnorrError::EmptyResponse` if there are no
+    /// responses at all and `SchnorrError::IndexOutOfBounds` if `idx` isn't less than the number of responses.
     pub fn get_response(&self, idx: usize) -> Result<&G::ScalarField, SchnorrError> {
-        if idx >= self.0.len() {
+        if self.0.is_empty() {
+            Err(SchnorrError::EmptyResponse)
+        } else if idx >= self.0.len() {
             Err(SchnorrError::IndexOutOfBounds(idx, self.0.len()))
         } else {
             Ok(&self.0[idx])
@@ -214,12 +496,187 @@ impl<G: AffineRepr> SchnorrResponse<G> {
     // TODO: Add function for challenge contribution (bytes that are hashed)
 }
 
+/// Return `a` if `cond` is true else `b`. With the `subtle` feature, the limbs are selected without branching on
+/// `cond`, but converting to and from `BigInt` isn't guaranteed to be constant time.
+#[cfg(feature = "subtle")]
+fn cond_select_scalar<F: PrimeField>(cond: bool, a: &F, b: &F) -> F {
+    use subtle::{Choice, ConditionallySelectable};
+
+    let choice = Choice::from(cond as u8);
+    let (a, b) = (a.into_bigint(), b.into_bigint());
+    let mut selected = b;
+    for (s, (a, b)) in selected
+        .as_mut()
+        .iter_mut()
+        .zip(a.as_ref().iter().zip(b.as_ref().iter()))
+    {
+        *s = u64::conditional_select(b, a, choice);
+    }
+    F::from_bigint(selected).unwrap()
+}
+
+#[cfg(not(feature = "subtle"))]
+fn cond_select_scalar<F: PrimeField>(cond: bool, a: &F, b: &F) -> F {
+    if cond {
+        *a
+    } else {
+        *b
+    }
+}
+
+/// Return the elements of `bases` at `indices` or `SchnorrError::IndexOutOfBounds` for the first index that
+/// isn't less than the number of bases.
+fn select_bases<G: AffineRepr>(bases: &[G], indices: &[usize]) -> Result<Vec<G>, SchnorrError> {
+    indices
+        .iter()
+        .map(|i| {
+            bases
+                .get(*i)
+                .copied()
+                .ok_or(SchnorrError::IndexOutOfBounds(*i, bases.len()))
+        })
+        .collect()
+}
+
+/// Return the distinct elements of `bases` and for each, the sum of the `scalars` at the positions it occurs
+/// in. The multi-scalar multiplication of the result is the same as of `bases` and `scalars`. Extra `bases`
+/// or `scalars` are ignored.
+fn sum_scalars_of_repeated_bases<G: AffineRepr>(
+    bases: &[G],
+    scalars: &[G::ScalarField],
+) -> (Vec<G>, Vec<G::ScalarField>) {
+    let mut positions = BTreeMap::<Vec<u8>, usize>::new();
+    let mut distinct_bases = Vec::new();
+    let mut summed_scalars = Vec::<G::ScalarField>::new();
+    for (b, s) in bases.iter().zip(scalars.iter()) {
+        let mut key = Vec::with_capacity(b.compressed_size());
+        b.serialize_compressed(&mut key)
+            .expect("serialization failed");
+        if let Some(&i) = positions.get(&key) {
+            summed_scalars[i] += s;
+        } else {
+            positions.insert(key, distinct_bases.len());
+            distinct_bases.push(*b);
+            summed_scalars.push(*s);
+        }
+    }
+    (distinct_bases, summed_scalars)
+}
+
 /// Uses try-and-increment. Vulnerable to side channel attacks. But this is only used when its input
 /// is public data.
 pub fn compute_random_oracle_challenge<F: PrimeField, D: Digest>(challenge_bytes: &[u8]) -> F {
     field_elem_from_try_and_incr::<F, D>(challenge_bytes)
 }
 
+/// Strategy for hashing the challenge bytes to a field element
+pub trait HashToFieldStrategy {
+    fn hash_to_field<F: PrimeField>(&self, bytes: &[u8]) -> F;
+}
+
+/// Try-and-increment as used by `compute_random_oracle_challenge`. Not constant time so only use with
+/// public data.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TryAndIncrement<D: Digest>(PhantomData<D>);
+
+/// `hash_to_field` of RFC 9380 using `expand_message_xmd` with the domain separation tag `dst`
+#[derive(Clone, Debug)]
+pub struct ExpandMessageXmd<D: FullDigest> {
+    pub dst: Vec<u8>,
+    _phantom: PhantomData<D>,
+}
+
+impl<D: Digest> TryAndIncrement<D> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: Digest> HashToFieldStrategy for TryAndIncrement<D> {
+    fn hash_to_field<F: PrimeField>(&self, bytes: &[u8]) -> F {
+        field_elem_from_try_and_incr::<F, D>(bytes)
+    }
+}
+
+impl<D: FullDigest> ExpandMessageXmd<D> {
+    pub fn new(dst: &[u8]) -> Self {
+        Self {
+            dst: dst.to_vec(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<D: FullDigest> HashToFieldStrategy for ExpandMessageXmd<D> {
+    fn hash_to_field<F: PrimeField>(&self, bytes: &[u8]) -> F {
+        hash_to_field::<F, D>(&self.dst, bytes)
+    }
+}
+
+/// Same as `compute_random_oracle_challenge` but the challenge bytes are hashed using the given `strategy`
+pub fn compute_random_oracle_challenge_with<F: PrimeField, S: HashToFieldStrategy>(
+    strategy: &S,
+    challenge_bytes: &[u8],
+) -> F {
+    strategy.hash_to_field(challenge_bytes)
+}
+
+/// Accumulates the contributions to the challenge, like over several rounds of a protocol, and creates the
+/// challenge from them as `compute_random_oracle_challenge` does.
+#[derive(Clone, Debug, Default)]
+pub struct ChallengeBuilder<D: Digest> {
+    bytes: Vec<u8>,
+    _phantom: PhantomData<D>,
+}
+
+impl<D: Digest> ChallengeBuilder<D> {
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Append the contribution returned by `challenge_contribution` of `contributor`
+    pub fn add_contributor(
+        &mut self,
+        contributor: &impl SchnorrChallengeContributor,
+    ) -> Result<(), SchnorrError> {
+        contributor.challenge_contribution(&mut self.bytes)
+    }
+
+    /// Append `bytes`, like the bases or the instance of the protocol
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Create the challenge from all the contributions added so far
+    pub fn finalize<F: PrimeField>(self) -> F {
+        compute_random_oracle_challenge::<F, D>(&self.bytes)
+    }
+}
+
+/// Bytes hashed to create the challenge, as written by the `challenge_contribution` functions. The prover and the
+/// verifier each create one and `Self::matches` reports when they hashed different bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChallengeContribution(pub Vec<u8>);
+
+impl ChallengeContribution {
+    /// Returns `SchnorrError::ChallengeContributionMismatch` if `other` has different bytes
+    pub fn matches(&self, other: &Self) -> Result<(), SchnorrError> {
+        if self == other {
+            Ok(())
+        } else {
+            Err(SchnorrError::ChallengeContributionMismatch)
+        }
+    }
+
+    /// Create the challenge from these bytes using `compute_random_oracle_challenge`
+    pub fn challenge<F: PrimeField, D: Digest>(&self) -> F {
+        compute_random_oracle_challenge::<F, D>(&self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,4 +755,584 @@ mod tests {
         test_schnorr_in_group!(G1Projective, G1Affine);
         test_schnorr_in_group!(G2Projective, G2Affine);
     }
+
+    #[test]
+    fn contribution_to_transcript() {
+        use dock_crypto_utils::transcript::new_merlin_transcript;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let comm_1 = SchnorrCommitment::new(&bases, (0..5).map(|_| Fr::rand(&mut rng)).collect());
+        let comm_2 = SchnorrCommitment::new(&bases, (0..5).map(|_| Fr::rand(&mut rng)).collect());
+
+        let mut bytes = vec![];
+        comm_1.challenge_contribution(&mut bytes).unwrap();
+        let mut expected = new_merlin_transcript(b"test");
+        expected.append_message(b"comm", &bytes);
+
+        let mut transcript_1 = new_merlin_transcript(b"test");
+        comm_1
+            .contribute_to_transcript(b"comm", &mut transcript_1)
+            .unwrap();
+        let mut transcript_2 = new_merlin_transcript(b"test");
+        comm_2
+            .contribute_to_transcript(b"comm", &mut transcript_2)
+            .unwrap();
+
+        let challenge = expected.challenge_scalar::<Fr>(b"challenge");
+        assert_eq!(transcript_1.challenge_scalar::<Fr>(b"challenge"), challenge);
+        assert_ne!(transcript_2.challenge_scalar::<Fr>(b"challenge"), challenge);
+    }
+
+    #[test]
+    fn commitment_from_iter() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let blindings = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let comm = SchnorrCommitment::new_from_iter(&bases, blindings.clone().into_iter());
+        assert_eq!(comm, SchnorrCommitment::new(&bases, blindings));
+
+        let mut rng_1 = StdRng::seed_from_u64(1u64);
+        let mut rng_2 = StdRng::seed_from_u64(1u64);
+        let comm = SchnorrCommitment::new_from_iter(&bases, (0..5).map(|_| Fr::rand(&mut rng_1)));
+        assert_eq!(
+            comm,
+            SchnorrCommitment::new(&bases, (0..5).map(|_| Fr::rand(&mut rng_2)).collect())
+        );
+    }
+
+    #[test]
+    fn repeated_bases() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let distinct = (0..3)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let bases = (0..12).map(|i| distinct[i % 3]).collect::<Vec<_>>();
+        let witnesses = (0..12).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let blindings = (0..12).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&bases, &witnesses).into_affine();
+
+        let comm = SchnorrCommitment::new_dedup(&bases, blindings.clone());
+        assert_eq!(comm, SchnorrCommitment::new(&bases, blindings));
+
+        let challenge = Fr::rand(&mut rng);
+        let mut resp = comm.response(&witnesses, &challenge).unwrap();
+        assert_eq!(resp.0.len(), 12);
+        resp.is_valid_dedup(&bases, &y, &comm.t, &challenge)
+            .unwrap();
+        resp.is_valid(&bases, &y, &comm.t, &challenge).unwrap();
+
+        resp.0[4] += Fr::from(1u64);
+        assert!(matches!(
+            resp.is_valid_dedup(&bases, &y, &comm.t, &challenge),
+            Err(SchnorrError::InvalidResponse)
+        ));
+    }
+
+    #[test]
+    fn challenge_builder() {
+        use blake2::Blake2b512;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..3)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let comm = SchnorrCommitment::new_from_iter(&bases, (0..3).map(|_| Fr::rand(&mut rng)));
+        let mut bytes = vec![];
+        comm.challenge_contribution(&mut bytes).unwrap();
+
+        let mut builder = ChallengeBuilder::<Blake2b512>::new();
+        builder.add_contributor(&comm).unwrap();
+        assert_eq!(
+            builder.finalize::<Fr>(),
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&bytes)
+        );
+
+        // Contributions added in several steps are same as concatenated
+        let comm_2 = SchnorrCommitment::new_from_iter(&bases, (0..3).map(|_| Fr::rand(&mut rng)));
+        let mut builder = ChallengeBuilder::<Blake2b512>::new();
+        builder.add_bytes(b"round 1");
+        builder.add_contributor(&comm).unwrap();
+        builder.add_bytes(b"round 2");
+        builder.add_contributor(&comm_2).unwrap();
+        let mut bytes = b"round 1".to_vec();
+        comm.challenge_contribution(&mut bytes).unwrap();
+        bytes.extend_from_slice(b"round 2");
+        comm_2.challenge_contribution(&mut bytes).unwrap();
+        assert_eq!(
+            builder.finalize::<Fr>(),
+            compute_random_oracle_challenge::<Fr, Blake2b512>(&bytes)
+        );
+    }
+
+    #[test]
+    fn reconstructed_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let witnesses = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&bases, &witnesses).into_affine();
+        let comm = SchnorrCommitment::new_from_iter(&bases, (0..5).map(|_| Fr::rand(&mut rng)));
+        let challenge = Fr::rand(&mut rng);
+        let mut resp = comm.response(&witnesses, &challenge).unwrap();
+        assert_eq!(
+            resp.reconstruct_commitment(&bases, &y, &challenge).unwrap(),
+            comm.t
+        );
+        assert!(matches!(
+            resp.reconstruct_commitment(&bases[..4], &y, &challenge),
+            Err(SchnorrError::ExpectedSameSizeSequences(5, 4))
+        ));
+
+        resp.0[0] += Fr::from(1u64);
+        assert_ne!(
+            resp.reconstruct_commitment(&bases, &y, &challenge).unwrap(),
+            comm.t
+        );
+    }
+
+    #[test]
+    fn partial_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let (a, b) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        assert_eq!(cond_select_scalar(true, &a, &b), a);
+        assert_eq!(cond_select_scalar(false, &a, &b), b);
+
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let disclosed = [false, true, false, true, true];
+        let witnesses = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&bases, &witnesses).into_affine();
+        let comm = SchnorrCommitment::new_partial(&mut rng, &bases, &disclosed);
+        assert_eq!(comm.blindings.len(), 5);
+        for (b, d) in comm.blindings.iter().zip(disclosed) {
+            assert_eq!(b.is_zero(), d);
+        }
+        assert_eq!(comm, SchnorrCommitment::new(&bases, comm.blindings.clone()));
+
+        let challenge = Fr::rand(&mut rng);
+        let resp = comm.response(&witnesses, &challenge).unwrap();
+        resp.is_valid(&bases, &y, &comm.t, &challenge).unwrap();
+        assert_eq!(resp.0[1], witnesses[1] * challenge);
+    }
+
+    #[test]
+    fn distributed_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..4)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let parties = 3;
+        // Each party has additive shares of the witnesses
+        let witness_shares = (0..parties)
+            .map(|_| (0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let witnesses = (0..4)
+            .map(|i| witness_shares.iter().map(|w| w[i]).sum::<Fr>())
+            .collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&bases, &witnesses).into_affine();
+
+        let comms = (0..parties)
+            .map(|_| SchnorrCommitment::new_from_iter(&bases, (0..4).map(|_| Fr::rand(&mut rng))))
+            .collect::<Vec<_>>();
+        let t = SchnorrCommitment::sum_commitments(&comms.iter().collect::<Vec<_>>());
+        assert_eq!(
+            t,
+            comms
+                .iter()
+                .map(|c| c.t.into_group())
+                .sum::<G1Projective>()
+                .into_affine()
+        );
+
+        let challenge = Fr::rand(&mut rng);
+        let resps = comms
+            .iter()
+            .zip(witness_shares.iter())
+            .map(|(c, w)| c.response(w, &challenge).unwrap())
+            .collect::<Vec<_>>();
+        let resp = SchnorrResponse::sum_responses(&resps.iter().collect::<Vec<_>>()).unwrap();
+        resp.is_valid(&bases, &y, &t, &challenge).unwrap();
+        assert!(matches!(
+            resp.is_valid(&bases, &y, &comms[0].t, &challenge),
+            Err(SchnorrError::InvalidResponse)
+        ));
+
+        let short = SchnorrResponse::<G1Affine>(vec![Fr::rand(&mut rng); 3]);
+        assert!(matches!(
+            SchnorrResponse::sum_responses(&[&resps[0], &short]),
+            Err(SchnorrError::ExpectedSameSizeSequences(3, 4))
+        ));
+        assert_eq!(
+            SchnorrResponse::<G1Affine>::sum_responses(&[])
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn selected_bases() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..10)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let indices = [7, 2, 5];
+        let witnesses = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let blindings = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let selected = indices.iter().map(|i| bases[*i]).collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&selected, &witnesses).into_affine();
+
+        let comm = SchnorrCommitment::new_selected(&bases, blindings.clone(), &indices).unwrap();
+        assert_eq!(comm, SchnorrCommitment::new(&selected, blindings.clone()));
+
+        let challenge = Fr::rand(&mut rng);
+        let resp = comm.response(&witnesses, &challenge).unwrap();
+        resp.is_valid_selected(&bases, &indices, &y, &comm.t, &challenge)
+            .unwrap();
+        assert!(matches!(
+            resp.is_valid_selected(&bases, &[7, 5, 2], &y, &comm.t, &challenge),
+            Err(SchnorrError::InvalidResponse)
+        ));
+        assert!(matches!(
+            resp.is_valid_selected(&bases, &[7, 2, 10], &y, &comm.t, &challenge),
+            Err(SchnorrError::IndexOutOfBounds(10, 10))
+        ));
+        assert!(matches!(
+            SchnorrCommitment::new_selected(&bases, blindings.clone(), &[7, 2]),
+            Err(SchnorrError::ExpectedSameSizeSequences(2, 3))
+        ));
+        assert!(matches!(
+            SchnorrCommitment::new_selected(&bases, blindings, &[7, 2, 11]),
+            Err(SchnorrError::IndexOutOfBounds(11, 10))
+        ));
+    }
+
+    #[test]
+    fn get_response() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let responses = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let resp = SchnorrResponse::<G1Affine>(responses.clone());
+        for (i, r) in responses.iter().enumerate() {
+            assert_eq!(resp.get_response(i).unwrap(), r);
+        }
+        assert!(matches!(
+            resp.get_response(3),
+            Err(SchnorrError::IndexOutOfBounds(3, 3))
+        ));
+        let empty = SchnorrResponse::<G1Affine>(vec![]);
+        assert!(matches!(
+            empty.get_response(0),
+            Err(SchnorrError::EmptyResponse)
+        ));
+    }
+
+    #[test]
+    fn malformed_bytes_deserialization() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..4)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let comm = SchnorrCommitment::new(&bases, (0..4).map(|_| Fr::rand(&mut rng)).collect());
+        let resp = comm
+            .response(
+                &(0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>(),
+                &Fr::rand(&mut rng),
+            )
+            .unwrap();
+        let mut comm_bytes = vec![];
+        comm.serialize_compressed(&mut comm_bytes).unwrap();
+        let mut resp_bytes = vec![];
+        resp.serialize_compressed(&mut resp_bytes).unwrap();
+
+        // Truncated and corrupted bytes give an error, never a panic
+        for i in 0..comm_bytes.len() {
+            assert!(
+                SchnorrCommitment::<G1Affine>::deserialize_compressed(&comm_bytes[..i]).is_err()
+            );
+            let mut corrupted = comm_bytes.clone();
+            corrupted[i] ^= 0xff;
+            let _ = SchnorrCommitment::<G1Affine>::deserialize_compressed(&corrupted[..]);
+            let _ = SchnorrCommitment::<G1Affine>::deserialize_compressed_unchecked(&corrupted[..]);
+        }
+        for i in 0..resp_bytes.len() {
+            assert!(SchnorrResponse::<G1Affine>::deserialize_compressed(&resp_bytes[..i]).is_err());
+            let mut corrupted = resp_bytes.clone();
+            corrupted[i] ^= 0xff;
+            let _ = SchnorrResponse::<G1Affine>::deserialize_compressed(&corrupted[..]);
+            let _ = SchnorrResponse::<G1Affine>::deserialize_compressed_unchecked(&corrupted[..]);
+        }
+
+        // A huge length doesn't allocate upfront and fails on running out of bytes
+        let mut huge = u64::MAX.to_le_bytes().to_vec();
+        huge.extend_from_slice(&resp_bytes[8..]);
+        assert!(SchnorrResponse::<G1Affine>::deserialize_compressed(&huge[..]).is_err());
+        assert!(
+            SchnorrCommitment::<G1Affine>::deserialize_uncompressed_unchecked(&huge[..]).is_err()
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn serialization_round_trip() {
+        use crate::test_utils::serialization_round_trip;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..4)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let comm = SchnorrCommitment::new(&bases, (0..4).map(|_| Fr::rand(&mut rng)).collect());
+        let resp = comm
+            .response(
+                &(0..4).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>(),
+                &Fr::rand(&mut rng),
+            )
+            .unwrap();
+        serialization_round_trip(&comm);
+        serialization_round_trip(&resp);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_objects() {
+        use arbitrary::{Arbitrary, Unstructured};
+        use ark_std::rand::RngCore;
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        for _ in 0..10 {
+            let mut data = vec![0u8; 1024];
+            rng.fill_bytes(&mut data);
+            let mut u = Unstructured::new(&data);
+            let comm = SchnorrCommitment::<G1Affine>::arbitrary(&mut u).unwrap();
+            let resp = SchnorrResponse::<G1Affine>::arbitrary(&mut u).unwrap();
+            let mut bytes = vec![];
+            comm.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(
+                SchnorrCommitment::<G1Affine>::deserialize_compressed(&bytes[..]).unwrap(),
+                comm
+            );
+            let mut bytes = vec![];
+            resp.serialize_compressed(&mut bytes).unwrap();
+            assert_eq!(
+                SchnorrResponse::<G1Affine>::deserialize_compressed(&bytes[..]).unwrap(),
+                resp
+            );
+        }
+    }
+
+    #[test]
+    fn validated_bases() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let mut bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let blindings = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        assert_eq!(
+            SchnorrCommitment::new_validated(&bases, blindings.clone()).unwrap(),
+            SchnorrCommitment::new(&bases, blindings.clone())
+        );
+
+        bases[3] = G1Affine::identity();
+        assert!(matches!(
+            SchnorrCommitment::new_validated(&bases, blindings.clone()),
+            Err(SchnorrError::InvalidBase(3))
+        ));
+
+        bases[3] = bases[1];
+        assert!(matches!(
+            SchnorrCommitment::new_validated(&bases, blindings),
+            Err(SchnorrError::DuplicateBase(1, 3))
+        ));
+    }
+
+    #[test]
+    fn projective_commitment() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let blindings = (0..3)
+            .map(|_| (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let comms = blindings
+            .iter()
+            .map(|b| SchnorrCommitment::new(&bases, b.clone()))
+            .collect::<Vec<_>>();
+        let comms_proj = blindings
+            .iter()
+            .map(|b| SchnorrCommitment::new_projective(&bases, b.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(comms_proj[0].clone().into_affine(), comms[0]);
+
+        // Sub-commitments can be added before converting to affine
+        let sum = comms_proj.iter().map(|c| c.t).sum::<G1Projective>();
+        assert_eq!(
+            sum.into_affine(),
+            comms
+                .iter()
+                .map(|c| c.t)
+                .sum::<G1Projective>()
+                .into_affine()
+        );
+
+        assert_eq!(
+            SchnorrCommitmentProjective::batch_into_affine(comms_proj),
+            comms
+        );
+    }
+
+    #[test]
+    fn challenge_wrapper() {
+        struct Challenge(Fr);
+
+        impl Borrow<Fr> for Challenge {
+            fn borrow(&self) -> &Fr {
+                &self.0
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..3)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let witnesses = (0..3).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&bases, &witnesses).into_affine();
+        let comm = SchnorrCommitment::new(&bases, (0..3).map(|_| Fr::rand(&mut rng)).collect());
+        let challenge = Challenge(Fr::rand(&mut rng));
+        let resp = comm.response(&witnesses, &challenge.0).unwrap();
+        resp.is_valid(&bases, &y, &comm.t, challenge).unwrap();
+
+        assert!(matches!(
+            resp.is_valid(&bases, &y, &comm.t, &Fr::zero()),
+            Err(SchnorrError::ZeroChallenge)
+        ));
+    }
+
+    #[test]
+    fn projective_y_and_t() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let bases = (0..5)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let witnesses = (0..5).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        // `y` as a sum that hasn't been normalized
+        let y = bases
+            .iter()
+            .zip(witnesses.iter())
+            .fold(G1Projective::zero(), |acc, (b, w)| acc + *b * w);
+        let comm =
+            SchnorrCommitmentProjective::new(&bases, (0..5).map(|_| Fr::rand(&mut rng)).collect());
+        let challenge = Fr::rand(&mut rng);
+        let resp = SchnorrResponse::<G1Affine>(
+            comm.blindings
+                .iter()
+                .zip(witnesses.iter())
+                .map(|(b, w)| *b + challenge * w)
+                .collect(),
+        );
+        resp.is_valid_projective(&bases, &y, &comm.t, &challenge)
+            .unwrap();
+        resp.is_valid(&bases, &y.into_affine(), &comm.t.into_affine(), &challenge)
+            .unwrap();
+
+        assert!(matches!(
+            resp.is_valid_projective(&bases, &y, &comm.t, &Fr::rand(&mut rng)),
+            Err(SchnorrError::InvalidResponse)
+        ));
+        assert!(matches!(
+            resp.is_valid_projective(&bases, &y, &comm.t, &Fr::zero()),
+            Err(SchnorrError::ZeroChallenge)
+        ));
+        assert!(matches!(
+            resp.is_valid_projective(&bases[1..], &y, &comm.t, &challenge),
+            Err(SchnorrError::ExpectedSameSizeSequences(5, 4))
+        ));
+    }
+
+    #[test]
+    fn hash_to_field_strategies() {
+        use blake2::Blake2b512;
+
+        let bytes = b"challenge bytes";
+        let default = compute_random_oracle_challenge::<Fr, Blake2b512>(bytes);
+        assert_eq!(
+            compute_random_oracle_challenge_with::<Fr, _>(
+                &TryAndIncrement::<Blake2b512>::new(),
+                bytes
+            ),
+            default
+        );
+
+        let xmd = ExpandMessageXmd::<Blake2b512>::new(b"test-dst");
+        let chal = compute_random_oracle_challenge_with::<Fr, _>(&xmd, bytes);
+        assert_eq!(chal, hash_to_field::<Fr, Blake2b512>(b"test-dst", bytes));
+        assert_eq!(
+            chal,
+            compute_random_oracle_challenge_with::<Fr, _>(&xmd, bytes)
+        );
+        assert_ne!(chal, default);
+        assert_ne!(
+            chal,
+            compute_random_oracle_challenge_with::<Fr, _>(
+                &ExpandMessageXmd::<Blake2b512>::new(b"other-dst"),
+                bytes
+            )
+        );
+    }
+}
+
+#[cfg(all(test, feature = "prop-tests"))]
+mod prop_tests {
+    use super::*;
+    use ark_bls12_381::{Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+    use ark_ec::VariableBaseMSM;
+    use ark_std::{
+        rand::{rngs::StdRng, SeedableRng},
+        UniformRand,
+    };
+    use proptest::prelude::*;
+
+    macro_rules! check_schnorr_in_group {
+        ($group_element_proj:ident, $group_element_affine:ident, $seed: expr, $count: expr, $tamper_idx: expr) => {{
+            let mut rng = StdRng::seed_from_u64($seed);
+            let bases = (0..$count)
+                .map(|_| $group_element_proj::rand(&mut rng).into_affine())
+                .collect::<Vec<$group_element_affine>>();
+            let witnesses = (0..$count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let blindings = (0..$count).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+            let challenge = Fr::rand(&mut rng);
+            let y = $group_element_proj::msm_unchecked(&bases, &witnesses).into_affine();
+
+            let comm = SchnorrCommitment::new(&bases, blindings);
+            let resp = comm.response(&witnesses, &challenge).unwrap();
+            prop_assert!(resp.is_valid(&bases, &y, &comm.t, &challenge).is_ok());
+
+            let mut tampered = resp.clone();
+            tampered.0[$tamper_idx % $count] += Fr::rand(&mut rng);
+            prop_assert!(tampered.is_valid(&bases, &y, &comm.t, &challenge).is_err());
+
+            // Responses with a different number of elements than the bases are rejected
+            prop_assert!(matches!(
+                resp.is_valid(&bases[1..], &y, &comm.t, &challenge),
+                Err(SchnorrError::ExpectedSameSizeSequences(_, _))
+            ));
+        }};
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn honest_responses_verify_and_tampered_fail(seed in any::<u64>(), count in 1usize..10, tamper_idx in any::<usize>()) {
+            check_schnorr_in_group!(G1Projective, G1Affine, seed, count, tamper_idx);
+            check_schnorr_in_group!(G2Projective, G2Affine, seed, count, tamper_idx);
+        }
+    }
 }
@@ -0,0 +1,31 @@
+//! Helpers for testing the objects of this crate and of crates built on it
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{fmt::Debug, vec::Vec};
+
+/// Serialize `obj` in the compressed and uncompressed canonical forms, deserialize it with and without
+/// validation and assert that each deserialized object is equal to `obj`. Panics on failure like `assert_eq`
+/// so it's meant to be called from tests.
+pub fn serialization_round_trip<
+    T: CanonicalSerialize + CanonicalDeserialize + PartialEq + Debug,
+>(
+    obj: &T,
+) {
+    let mut bytes = Vec::with_capacity(obj.compressed_size());
+    obj.serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), obj.compressed_size());
+    assert_eq!(&T::deserialize_compressed(&bytes[..]).unwrap(), obj);
+    assert_eq!(
+        &T::deserialize_compressed_unchecked(&bytes[..]).unwrap(),
+        obj
+    );
+
+    let mut bytes = Vec::with_capacity(obj.uncompressed_size());
+    obj.serialize_uncompressed(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), obj.uncompressed_size());
+    assert_eq!(&T::deserialize_uncompressed(&bytes[..]).unwrap(), obj);
+    assert_eq!(
+        &T::deserialize_uncompressed_unchecked(&bytes[..]).unwrap(),
+        obj
+    );
+}
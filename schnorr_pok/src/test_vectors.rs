@@ -0,0 +1,112 @@
+//! Test vectors of the Schnorr protocol for checking compatibility of other implementations.
+//! All elements are serialized in the compressed canonical form of arkworks and appear in JSON as byte
+//! arrays. Field elements are 32 bytes little-endian and G1 elements are 48 bytes in the Zcash format for
+//! BLS12-381, i.e. the big-endian x coordinate with flags in the 3 most significant bits.
+//! The verification equation is `bases[0]*responses[0] + ... + bases[n]*responses[n] - y*challenge == t`
+
+use crate::{error::SchnorrError, SchnorrCommitment, SchnorrResponse};
+use ark_bls12_381::{Fr, G1Affine, G1Projective};
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    string::String,
+    vec::Vec,
+    UniformRand,
+};
+use dock_crypto_utils::serde_utils::ArkObjectBytes;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Seed of the RNG used to generate the test vector
+pub const SEED: u64 = 0;
+
+/// Number of discrete logs whose knowledge is proven in the test vector
+pub const WITNESS_COUNT: usize = 5;
+
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchnorrTestVector {
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub bases: Vec<G1Affine>,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub witnesses: Vec<Fr>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub y: G1Affine,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub blindings: Vec<Fr>,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub t: G1Affine,
+    #[serde_as(as = "ArkObjectBytes")]
+    pub challenge: Fr,
+    #[serde_as(as = "Vec<ArkObjectBytes>")]
+    pub responses: Vec<Fr>,
+}
+
+impl SchnorrTestVector {
+    /// Generate the test vector for BLS12-381 G1 using an RNG seeded with `SEED`
+    pub fn generate() -> Self {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let bases = (0..WITNESS_COUNT)
+            .map(|_| G1Projective::rand(&mut rng).into_affine())
+            .collect::<Vec<_>>();
+        let witnesses = (0..WITNESS_COUNT)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let y = G1Projective::msm_unchecked(&bases, &witnesses).into_affine();
+        let blindings = (0..WITNESS_COUNT)
+            .map(|_| Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let comm = SchnorrCommitment::new(&bases, blindings.clone());
+        let challenge = Fr::rand(&mut rng);
+        let responses = comm.response(&witnesses, &challenge).unwrap().0;
+        Self {
+            bases,
+            witnesses,
+            y,
+            blindings,
+            t: comm.t,
+            challenge,
+            responses,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Check that the responses are valid for the bases, `y`, `t` and challenge
+    pub fn verify(&self) -> Result<(), SchnorrError> {
+        SchnorrResponse::<G1Affine>(self.responses.clone()).is_valid(
+            &self.bases,
+            &self.y,
+            &self.t,
+            &self.challenge,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLS12_381_TEST_VECTOR: &str = include_str!("../test_vectors/bls12_381.json");
+
+    #[test]
+    fn test_vector() {
+        let vector = SchnorrTestVector::generate();
+        vector.verify().unwrap();
+
+        // The checked in test vector is the one generated and verifies
+        let expected = SchnorrTestVector::from_json(BLS12_381_TEST_VECTOR).unwrap();
+        assert_eq!(vector, expected);
+        expected.verify().unwrap();
+        assert_eq!(
+            SchnorrTestVector::from_json(&vector.to_json()).unwrap(),
+            vector
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use ark_ec::{scalar_mul::fixed_base::FixedBase, CurveGroup};
+use ark_ec::{scalar_mul::fixed_base::FixedBase, AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::{fmt::Debug, vec::Vec};
@@ -60,6 +60,26 @@ pub fn multiply_field_elems_with_same_group_elem<G: CurveGroup>(
     table.multiply_many(elements)
 }
 
+/// Inputs of size at most this are multiplied naively by `smart_msm`
+pub const NAIVE_MSM_THRESHOLD: usize = 2;
+
+/// Multi-scalar multiplication which multiplies each base with its scalar and sums the results when the
+/// number of bases is at most `NAIVE_MSM_THRESHOLD` (this is faster for tiny inputs as it avoids the bucket setup)
+/// and uses `VariableBaseMSM` otherwise. Like `VariableBaseMSM::msm_unchecked`, the extra elements of the
+/// longer slice are ignored if the slices have different lengths.
+pub fn smart_msm<G: AffineRepr>(bases: &[G], scalars: &[G::ScalarField]) -> G::Group {
+    let size = bases.len().min(scalars.len());
+    if size <= NAIVE_MSM_THRESHOLD {
+        bases
+            .iter()
+            .zip(scalars.iter())
+            .map(|(b, s)| b.mul_bigint(s.into_bigint()))
+            .sum()
+    } else {
+        G::Group::msm_unchecked(&bases[..size], &scalars[..size])
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -308,4 +328,44 @@ pub mod tests {
         println!("d15={:?}", d15);
         println!("d16={:?}", d16);
     }
+
+    #[test]
+    fn timing_smart_msm() {
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let iters = 100;
+        for size in 1..=8 {
+            let mut naive = Duration::default();
+            let mut msm = Duration::default();
+            let mut smart = Duration::default();
+            for _ in 0..iters {
+                let bases = (0..size)
+                    .map(|_| G1::rand(&mut rng).into_affine())
+                    .collect::<Vec<_>>();
+                let scalars = (0..size).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+
+                let start = Instant::now();
+                let r1 = bases
+                    .iter()
+                    .zip(scalars.iter())
+                    .map(|(b, s)| b.mul_bigint(s.into_bigint()))
+                    .sum::<G1>();
+                naive += start.elapsed();
+
+                let start = Instant::now();
+                let r2 = G1::msm_unchecked(&bases, &scalars);
+                msm += start.elapsed();
+
+                let start = Instant::now();
+                let r3 = smart_msm(&bases, &scalars);
+                smart += start.elapsed();
+
+                assert_eq!(r1, r2);
+                assert_eq!(r1, r3);
+            }
+            println!(
+                "For {} bases, naive={:?}, VariableBaseMSM={:?}, smart_msm={:?}",
+                size, naive, msm, smart
+            );
+        }
+    }
 }
@@ -47,6 +47,10 @@ pub enum VBAccumulatorError {
     SSError(SSError),
     OTError(OTError),
     MissingSchnorrResponseForElement,
+    /// The initial element at the given index is 0
+    ZeroInitialElement(usize),
+    /// The initial elements at the given indices are equal
+    DuplicateInitialElements(usize, usize),
 }
 
 impl From<SchnorrError> for VBAccumulatorError {
@@ -66,6 +66,7 @@ use crate::{
     persistence::{InitialElementsStore, State, UniversalAccumulatorState},
     positive::Accumulator,
     setup::{PublicKey, SecretKey, SetupParams},
+    universal_init_constants::AccumulatorInitialElements,
     witness::NonMembershipWitness,
 };
 use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
@@ -154,6 +155,24 @@ impl<G: AffineRepr> UniversalAccumulator<G> {
         Self { V, f_V, max_size }
     }
 
+    /// Same as `Self::initialize` but the initial elements `xs` are the ones of the curve `C`
+    pub fn initialize_for_curve<R: RngCore, C: AccumulatorInitialElements<G::ScalarField>>(
+        rng: &mut R,
+        params_gen: impl AsRef<G>,
+        max_size: u64,
+        sk: &SecretKey<G::ScalarField>,
+        initial_elements_store: &mut dyn InitialElementsStore<G::ScalarField>,
+    ) -> Self {
+        Self::initialize(
+            rng,
+            params_gen,
+            max_size,
+            sk,
+            C::initial_elements(),
+            initial_elements_store,
+        )
+    }
+
     /// Create a new universal accumulator. Given the max size, it generates `max_size+1` initial elements
     /// *randomly* and adds them to the accumulator; these initial elements can never be removed or
     /// added back to the accumulator. Generating all elements *randomly* deviates from the paper and
@@ -770,6 +789,66 @@ pub mod tests {
         }
     }
 
+    struct Bls12_377Elements;
+
+    impl AccumulatorInitialElements<ark_bls12_377::Fr> for Bls12_377Elements {
+        fn initial_elements() -> Vec<ark_bls12_377::Fr> {
+            use ark_bls12_377::Fr;
+            initial_elements_for_bls12_377!(Fr)
+        }
+    }
+
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn initialization_for_bls12_381() {
+        let max = 50;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        let params = SetupParams::<Bls12_381>::generate_using_rng(&mut rng);
+        let keypair = Keypair::<Bls12_381>::generate_using_rng(&mut rng, &params);
+        let mut initial_elements = InMemoryInitialElements::new();
+        let accumulator = UniversalAccumulator::<G1Affine>::initialize_for_curve::<_, Bls12_381>(
+            &mut rng,
+            &params,
+            max,
+            &keypair.secret_key,
+            &mut initial_elements,
+        );
+        let initial = Bls12_381::initial_elements();
+        assert_eq!(initial, initial_elements_for_bls12_381!(Fr));
+        assert_eq!(initial_elements.db.len(), max as usize + initial.len() + 1);
+        for i in initial {
+            assert!(!accumulator.is_element_acceptable(&i, &initial_elements));
+        }
+    }
+
+    #[test]
+    fn initialization_for_curve() {
+        let max = 50;
+        let mut rng = StdRng::seed_from_u64(0u64);
+
+        // A curve whose initial elements are defined outside this crate
+        let params = SetupParams::<ark_bls12_377::Bls12_377>::generate_using_rng(&mut rng);
+        let keypair = Keypair::<ark_bls12_377::Bls12_377>::generate_using_rng(&mut rng, &params);
+        let mut initial_elements = InMemoryInitialElements::new();
+        let accumulator = UniversalAccumulator::<ark_bls12_377::G1Affine>::initialize_for_curve::<
+            _,
+            Bls12_377Elements,
+        >(
+            &mut rng,
+            &params,
+            max,
+            &keypair.secret_key,
+            &mut initial_elements,
+        );
+        let initial = Bls12_377Elements::initial_elements();
+        assert_eq!(initial.len(), 8);
+        assert_eq!(initial_elements.db.len(), max as usize + initial.len() + 1);
+        for i in initial {
+            assert!(!accumulator.is_element_acceptable(&i, &initial_elements));
+        }
+    }
+
     #[test]
     fn membership_non_membership() {
         // Test to check membership and non-membership in accumulator
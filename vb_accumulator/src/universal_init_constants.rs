@@ -3,7 +3,24 @@
 //! where `p` is the curve order
 //! Generated using the Sage file in this package
 
-use ark_std::str;
+use ark_ff::PrimeField;
+use ark_std::{str, vec::Vec};
+
+/// Initial elements of the universal accumulator for the scalar field `F` of a curve. Implement this for a curve
+/// to use `UniversalAccumulator::initialize_for_curve` with it. It's implemented for `Bls12_381` when the feature
+/// `bls12-381` is enabled.
+pub trait AccumulatorInitialElements<F: PrimeField> {
+    fn initial_elements() -> Vec<F>;
+}
+
+#[cfg(feature = "bls12-381")]
+impl AccumulatorInitialElements<ark_bls12_381::Fr> for ark_bls12_381::Bls12_381 {
+    fn initial_elements() -> Vec<ark_bls12_381::Fr> {
+        use ark_bls12_381::Fr;
+        use ark_ff::MontFp;
+        crate::initial_elements_for_bls12_381!(Fr)
+    }
+}
 
 /// For curve BLS12-381
 pub static BLS12_381: [&str; 12] = [
@@ -1,3 +1,13 @@
+use crate::error::VBAccumulatorError;
+use ark_ff::PrimeField;
+use ark_std::{collections::BTreeMap, vec::Vec};
+use digest::Digest;
+use dock_crypto_utils::{
+    concat_slices, hashing_utils::field_elem_from_try_and_incr, serde_utils::ArkObjectBytes,
+};
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
 // TODO: Following is the correct way to generate params but does not work
 /*#[macro_export]
 macro_rules! initial_elements {
@@ -10,44 +20,332 @@ macro_rules! initial_elements {
     }};
 }*/
 
+/// Initial elements for the scalar field of BLS12-381 as a fixed size array. As `MontFp` is evaluated at
+/// compile time, this can be used to initialize a `const` or `static` without allocation.
+#[macro_export]
+macro_rules! initial_elements_array_for_bls12_381 {
+    ($Fr: ident) => {{
+        let res: [$Fr; 12] = [
+            MontFp!(
+                "48702668752744056212524131907777588138266291225961714032791501307766539426092"
+            ),
+            MontFp!("228988810152649578064853576960394133503"),
+            MontFp!(
+                "46454669306535580442819773933076400553933878175571986080969841567332895786017"
+            ),
+            MontFp!(
+                "45205798410695891408614168931997362660425926808089717551136866582122865413073"
+            ),
+            MontFp!(
+                "34288838479222975534395827096705373067917615411464237264633937766591872085112"
+            ),
+            MontFp!("92992352668298031901896044184055569161836568729514436393284062001611460666"),
+            MontFp!(
+                "24304788127629790681951745309274362165984411810782330025478878507999651843060"
+            ),
+            MontFp!(
+                "23682614706182920393234601202846423393145397933621008028884956856015126827098"
+            ),
+            MontFp!(
+                "47983764596765232981592716782054734184223420327454065257761821043292139139799"
+            ),
+            MontFp!(
+                "16637449727034532026669502917734913967753061896878734983059700689089422192450"
+            ),
+            MontFp!(
+                "49543903940095721467774728926921205375104051833932232540372393802570589366958"
+            ),
+            MontFp!(
+                "26899426916892720090059971370452737574507152537975350262743684140510311482771"
+            ),
+        ];
+        res
+    }};
+}
+
+/// Initial elements for the scalar field of BLS12-381, same as `universal_init_constants::BLS12_381`.
+/// The decimal literals are converted to limbs when the macro is expanded so no parsing happens at
+/// runtime. To share a single copy across accumulators, put `initial_elements_array_for_bls12_381` in a
+/// `static` and use a slice of it.
 #[macro_export]
 macro_rules! initial_elements_for_bls12_381 {
+    ($Fr: ident) => {{
+        let res: Vec<$Fr> = $crate::initial_elements_array_for_bls12_381!($Fr).to_vec();
+        res
+    }};
+}
+
+/// Initial elements for the scalar field of BLS12-377, same as `universal_init_constants::BLS12_377`
+#[macro_export]
+macro_rules! initial_elements_for_bls12_377 {
     ($Fr: ident) => {{
         let mut res: Vec<$Fr> = vec![];
         res.push(MontFp!(
-            "48702668752744056212524131907777588138266291225961714032791501307766539426092"
+            "7795940565250490195249921628478673235650072388237803151651170553000045032878"
+        ));
+        res.push(MontFp!(
+            "8444461749428370424248824938781546531284005582649182570233710176290576793600"
+        ));
+        res.push(MontFp!(
+            "4745518175903757463842934090568458886640490686013927270241981030307505801190"
+        ));
+        res.push(MontFp!(
+            "3806160955094562116421050616880809404450202398977431724757427984453760640339"
         ));
-        res.push(MontFp!("228988810152649578064853576960394133503"));
         res.push(MontFp!(
-            "46454669306535580442819773933076400553933878175571986080969841567332895786017"
+            "5531368429156421952110876480206518443192767620343255557439134155258810716115"
         ));
         res.push(MontFp!(
-            "45205798410695891408614168931997362660425926808089717551136866582122865413073"
+            "2555005339038708526268282801444752183683232191568776724209619804738517376664"
         ));
         res.push(MontFp!(
-            "34288838479222975534395827096705373067917615411464237264633937766591872085112"
+            "8194342840065409597871291143023280309873830043437932524835223451409642652018"
         ));
         res.push(MontFp!(
-            "92992352668298031901896044184055569161836568729514436393284062001611460666"
+            "3578726610214780094152739826015499689470341984120488618224445550117270472611"
         ));
+        res
+    }};
+}
+
+/// Initial elements for the scalar field of BN254, same as `universal_init_constants::BN_254`
+#[macro_export]
+macro_rules! initial_elements_for_bn254 {
+    ($Fr: ident) => {{
+        let mut res: Vec<$Fr> = vec![];
         res.push(MontFp!(
-            "24304788127629790681951745309274362165984411810782330025478878507999651843060"
+            "13230970562374477902245634650304843348158009037087300592476806988148848886068"
         ));
         res.push(MontFp!(
-            "23682614706182920393234601202846423393145397933621008028884956856015126827098"
+            "21709117141928588693832876689996046447131590296091612788258132810434292988501"
         ));
         res.push(MontFp!(
-            "47983764596765232981592716782054734184223420327454065257761821043292139139799"
+            "2813847780979723574988245613652680993090845345239261844935281625931194923049"
         ));
         res.push(MontFp!(
-            "16637449727034532026669502917734913967753061896878734983059700689089422192450"
+            "21075223181761470858392280230640509312725772649368557035802272817356099371378"
         ));
         res.push(MontFp!(
-            "49543903940095721467774728926921205375104051833932232540372393802570589366958"
+            "18575529338540977858279584707709083774445081524016629449594345011666303179009"
         ));
         res.push(MontFp!(
-            "26899426916892720090059971370452737574507152537975350262743684140510311482771"
+            "3010795248590460775098706906216088335837662712515274137377572883540288047120"
+        ));
+        res.push(MontFp!(
+            "4958134629056206981864641789723247959168088092324913192269967810272557241707"
+        ));
+        res.push(MontFp!(
+            "3411709658520273806660579524158640576914152436095851016475605690184385220473"
+        ));
+        res.push(MontFp!(
+            "9161640646677986258142093669799443634569749348249541686595670049465328739673"
+        ));
+        res.push(MontFp!(
+            "20481465387535692543019606222736209278578496458529912499887026365520049872736"
         ));
         res
     }};
 }
+
+/// Deterministically generate `count` distinct, non-zero field elements to be used as initial elements
+/// of a universal accumulator. The `i`-th candidate is derived by hashing `domain || i` (with `i` as a
+/// little-endian `u64`) using `field_elem_from_try_and_incr`; candidates that are 0 or repeat an earlier
+/// element are skipped. Unlike the per-curve macros, this works for any field.
+pub fn generate_initial_elements<F: PrimeField, D: Digest>(count: usize, domain: &[u8]) -> Vec<F> {
+    let mut elems = Vec::with_capacity(count);
+    let mut i = 0u64;
+    while elems.len() < count {
+        let e = field_elem_from_try_and_incr::<F, D>(&concat_slices!(domain, i.to_le_bytes()));
+        if !e.is_zero() && !elems.contains(&e) {
+            elems.push(e);
+        }
+        i += 1;
+    }
+    elems
+}
+
+/// Check that the given initial elements are non-zero and pairwise distinct. Returns the index of the
+/// first zero element or the indices of the first pair of equal elements.
+pub fn validate_initial_elements<F: PrimeField>(elems: &[F]) -> Result<(), VBAccumulatorError> {
+    let mut seen = BTreeMap::new();
+    for (i, e) in elems.iter().enumerate() {
+        if e.is_zero() {
+            return Err(VBAccumulatorError::ZeroInitialElement(i));
+        }
+        if let Some(j) = seen.insert(e, i) {
+            return Err(VBAccumulatorError::DuplicateInitialElements(j, i));
+        }
+    }
+    Ok(())
+}
+
+/// Return the index of `candidate` in the initial elements `elems` if it is one of them. Such elements are
+/// reserved and should not be added to or removed from the accumulator.
+pub fn initial_element_index<F: PrimeField>(elems: &[F], candidate: &F) -> Option<usize> {
+    elems.iter().position(|e| e == candidate)
+}
+
+/// Serialize initial elements with serde as a sequence of canonically serialized field elements. Can be
+/// used with `#[serde(serialize_with = "serialize_initial_elements")]`.
+pub fn serialize_initial_elements<F: PrimeField, S: Serializer>(
+    elems: &[F],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    <[ArkObjectBytes] as SerializeAs<[F]>>::serialize_as(elems, serializer)
+}
+
+/// Deserialize initial elements serialized with `serialize_initial_elements`. Can be used with
+/// `#[serde(deserialize_with = "deserialize_initial_elements")]`.
+pub fn deserialize_initial_elements<'de, F: PrimeField, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<F>, D::Error> {
+    <Vec<ArkObjectBytes> as DeserializeAs<'de, Vec<F>>>::deserialize_as(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{MontFp, Zero};
+    use ark_std::{collections::BTreeSet, str::FromStr};
+    use blake2::Blake2b512;
+
+    #[test]
+    fn initial_elements_array_bls12_381() {
+        use ark_bls12_381::Fr;
+
+        const INITIAL: [Fr; 12] = initial_elements_array_for_bls12_381!(Fr);
+        static SHARED: [Fr; 12] = initial_elements_array_for_bls12_381!(Fr);
+        let initial: Vec<Fr> = initial_elements_for_bls12_381!(Fr);
+        assert_eq!(INITIAL.to_vec(), initial);
+        let shared: &'static [Fr] = &SHARED;
+        assert_eq!(shared, initial.as_slice());
+        for (e, c) in INITIAL
+            .iter()
+            .zip(crate::universal_init_constants::BLS12_381)
+        {
+            assert_eq!(*e, Fr::from_str(c).unwrap());
+        }
+    }
+
+    #[test]
+    fn initial_elements_bls12_377() {
+        use ark_bls12_377::Fr;
+
+        let initial: Vec<Fr> = initial_elements_for_bls12_377!(Fr);
+        assert_eq!(initial.len(), 8);
+        assert!(initial.iter().all(|e| !e.is_zero()));
+        assert_eq!(initial.iter().collect::<BTreeSet<_>>().len(), initial.len());
+        for (e, c) in initial
+            .iter()
+            .zip(crate::universal_init_constants::BLS12_377)
+        {
+            assert_eq!(*e, Fr::from_str(c).unwrap());
+        }
+    }
+
+    #[test]
+    fn initial_elements_bn254() {
+        use ark_bn254::Fr;
+
+        // The number of elements depends on the factorisation of the curve order minus 1 so it differs
+        // across curves
+        let initial: Vec<Fr> = initial_elements_for_bn254!(Fr);
+        assert_eq!(initial.len(), crate::universal_init_constants::BN_254.len());
+        assert!(initial.iter().all(|e| !e.is_zero()));
+        assert_eq!(initial.iter().collect::<BTreeSet<_>>().len(), initial.len());
+        for (e, c) in initial.iter().zip(crate::universal_init_constants::BN_254) {
+            assert_eq!(*e, Fr::from_str(c).unwrap());
+        }
+    }
+
+    #[test]
+    fn generated_initial_elements() {
+        use ark_bls12_381::Fr;
+
+        let elems = generate_initial_elements::<Fr, Blake2b512>(20, b"test");
+        assert_eq!(elems.len(), 20);
+        assert!(elems.iter().all(|e| !e.is_zero()));
+        assert_eq!(elems.iter().collect::<BTreeSet<_>>().len(), elems.len());
+
+        // Deterministic and a prefix of a larger set
+        assert_eq!(
+            elems,
+            generate_initial_elements::<Fr, Blake2b512>(20, b"test")
+        );
+        assert_eq!(
+            elems[..10],
+            generate_initial_elements::<Fr, Blake2b512>(10, b"test")[..]
+        );
+        assert_ne!(
+            elems,
+            generate_initial_elements::<Fr, Blake2b512>(20, b"other")
+        );
+    }
+
+    #[test]
+    fn initial_elements_validation() {
+        use ark_bls12_381::Fr;
+
+        let initial: Vec<Fr> = initial_elements_for_bls12_381!(Fr);
+        validate_initial_elements(&initial).unwrap();
+        validate_initial_elements(&generate_initial_elements::<Fr, Blake2b512>(20, b"test"))
+            .unwrap();
+
+        let mut elems = initial.clone();
+        elems[5] = Fr::zero();
+        assert!(matches!(
+            validate_initial_elements(&elems),
+            Err(VBAccumulatorError::ZeroInitialElement(5))
+        ));
+
+        let mut elems = initial.clone();
+        elems[7] = elems[2];
+        elems[9] = elems[1];
+        assert!(matches!(
+            validate_initial_elements(&elems),
+            Err(VBAccumulatorError::DuplicateInitialElements(2, 7))
+        ));
+    }
+
+    #[test]
+    fn find_initial_element() {
+        use ark_bls12_381::Fr;
+        use ark_std::{
+            rand::{rngs::StdRng, SeedableRng},
+            UniformRand,
+        };
+
+        let mut rng = StdRng::seed_from_u64(0u64);
+        let initial: Vec<Fr> = initial_elements_for_bls12_381!(Fr);
+        for (i, e) in initial.iter().enumerate() {
+            assert_eq!(initial_element_index(&initial, e), Some(i));
+        }
+        assert_eq!(initial_element_index(&initial, &Fr::rand(&mut rng)), None);
+    }
+
+    #[test]
+    fn initial_elements_serde() {
+        use ark_bls12_381::Fr;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Serialize, Deserialize)]
+        struct Params {
+            #[serde(
+                serialize_with = "serialize_initial_elements",
+                deserialize_with = "deserialize_initial_elements"
+            )]
+            initial: Vec<Fr>,
+        }
+
+        let params = Params {
+            initial: initial_elements_for_bls12_381!(Fr),
+        };
+        let ser = serde_json::to_string(&params).unwrap();
+        let deser = serde_json::from_str::<Params>(&ser).unwrap();
+        assert_eq!(deser.initial, initial_elements_for_bls12_381!(Fr));
+
+        let ser = rmp_serde::to_vec_named(&params).unwrap();
+        let deser = rmp_serde::from_slice::<Params>(&ser).unwrap();
+        assert_eq!(deser.initial, initial_elements_for_bls12_381!(Fr));
+    }
+}